@@ -0,0 +1,693 @@
+use crate::convert::{ConversionError, ToAlloy, ToEthers, TryToAlloy, TryToEthers};
+use alloy::dyn_abi::{DynSolType, DynSolValue};
+use alloy::json_abi::{InternalType, JsonAbi};
+use alloy::primitives::{B256, I256};
+use ethers::abi::{Abi, ParamType, StateMutability, Token};
+
+/// Byte length of an abi `function` value, 20 bytes address + 4 bytes selector
+const FUNCTION_LENGTH: usize = 24;
+
+impl ToAlloy for ParamType {
+    type To = DynSolType;
+
+    fn to_alloy(self) -> Self::To {
+        match self {
+            ParamType::Address => DynSolType::Address,
+            ParamType::Bytes => DynSolType::Bytes,
+            ParamType::Int(size) => DynSolType::Int(size),
+            ParamType::Uint(size) => DynSolType::Uint(size),
+            ParamType::Bool => DynSolType::Bool,
+            ParamType::String => DynSolType::String,
+            ParamType::Array(inner) => DynSolType::Array(Box::new(inner.to_alloy())),
+            ParamType::FixedBytes(size) => DynSolType::FixedBytes(size),
+            ParamType::FixedArray(inner, size) => {
+                DynSolType::FixedArray(Box::new(inner.to_alloy()), size)
+            }
+            ParamType::Tuple(inner) => DynSolType::Tuple(inner.to_alloy()),
+        }
+    }
+}
+
+impl ToAlloy for Box<ParamType> {
+    type To = DynSolType;
+
+    fn to_alloy(self) -> Self::To {
+        (*self).to_alloy()
+    }
+}
+
+/// ethers has no `function` type, it is converted to its `bytes24` encoding
+impl TryToEthers<ParamType> for DynSolType {
+    fn try_to_ethers(self) -> Result<ParamType, ConversionError> {
+        Ok(match self {
+            DynSolType::Bool => ParamType::Bool,
+            DynSolType::Int(size) => ParamType::Int(size),
+            DynSolType::Uint(size) => ParamType::Uint(size),
+            DynSolType::FixedBytes(size) => ParamType::FixedBytes(size),
+            DynSolType::Address => ParamType::Address,
+            DynSolType::Function => ParamType::FixedBytes(FUNCTION_LENGTH),
+            DynSolType::Bytes => ParamType::Bytes,
+            DynSolType::String => ParamType::String,
+            DynSolType::Array(inner) => ParamType::Array(Box::new((*inner).try_to_ethers()?)),
+            DynSolType::FixedArray(inner, size) => {
+                ParamType::FixedArray(Box::new((*inner).try_to_ethers()?), size)
+            }
+            DynSolType::Tuple(inner) => ParamType::Tuple(inner.try_to_ethers()?),
+            #[allow(unreachable_patterns)]
+            other => {
+                return Err(ConversionError::UnsupportedAbi(
+                    other.sol_type_name().into(),
+                ))
+            }
+        })
+    }
+}
+
+/// Converts an untyped ethers [Token] into a [DynSolValue], since tokens do not
+/// carry the bit size of integers they are assumed to be 256 bits, use
+/// [ethers_token_to_alloy_typed] to get the exact sizes of a known abi type
+impl TryToAlloy<DynSolValue> for Token {
+    fn try_to_alloy(self) -> Result<DynSolValue, ConversionError> {
+        Ok(match self {
+            Token::Address(address) => DynSolValue::Address(address.to_alloy()),
+            Token::FixedBytes(bytes) => {
+                let size = bytes.len();
+                DynSolValue::FixedBytes(fixed_bytes_word(bytes)?, size)
+            }
+            Token::Bytes(bytes) => DynSolValue::Bytes(bytes),
+            Token::Int(value) => DynSolValue::Int(I256::from_raw(value.to_alloy()), 256),
+            Token::Uint(value) => DynSolValue::Uint(value.to_alloy(), 256),
+            Token::Bool(value) => DynSolValue::Bool(value),
+            Token::String(value) => DynSolValue::String(value),
+            Token::FixedArray(tokens) => DynSolValue::FixedArray(tokens.try_to_alloy()?),
+            Token::Array(tokens) => DynSolValue::Array(tokens.try_to_alloy()?),
+            Token::Tuple(tokens) => DynSolValue::Tuple(tokens.try_to_alloy()?),
+        })
+    }
+}
+
+impl TryToEthers<Token> for DynSolValue {
+    fn try_to_ethers(self) -> Result<Token, ConversionError> {
+        Ok(match self {
+            DynSolValue::Bool(value) => Token::Bool(value),
+            DynSolValue::Int(value, _) => Token::Int(value.into_raw().to_ethers()),
+            DynSolValue::Uint(value, _) => Token::Uint(value.to_ethers()),
+            DynSolValue::FixedBytes(word, size) => Token::FixedBytes(word[..size].to_vec()),
+            DynSolValue::Address(address) => Token::Address(address.to_ethers()),
+            DynSolValue::Function(function) => Token::FixedBytes(function.to_vec()),
+            DynSolValue::Bytes(bytes) => Token::Bytes(bytes),
+            DynSolValue::String(value) => Token::String(value),
+            DynSolValue::Array(values) => Token::Array(values.try_to_ethers()?),
+            DynSolValue::FixedArray(values) => Token::FixedArray(values.try_to_ethers()?),
+            DynSolValue::Tuple(values) => Token::Tuple(values.try_to_ethers()?),
+            #[allow(unreachable_patterns)]
+            other => return Err(ConversionError::UnsupportedAbi(format!("{:?}", other))),
+        })
+    }
+}
+
+/// Converts an ethers [Token] into a [DynSolValue] of the given ethers
+/// [ParamType], checking the token against the type along the way so that
+/// integer sizes, `bytesN` lengths and array lengths are exact
+pub fn ethers_token_to_alloy_typed(
+    token: Token,
+    param_type: &ParamType,
+) -> Result<DynSolValue, ConversionError> {
+    token_to_alloy_typed(token, &param_type.clone().to_alloy())
+}
+
+fn token_to_alloy_typed(token: Token, ty: &DynSolType) -> Result<DynSolValue, ConversionError> {
+    let mismatch = |token: &Token| ConversionError::AbiTypeMismatch {
+        expected: ty.sol_type_name().into(),
+        value: format!("{:?}", token),
+    };
+
+    Ok(match (token, ty) {
+        (Token::Address(address), DynSolType::Address) => DynSolValue::Address(address.to_alloy()),
+        (Token::Bool(value), DynSolType::Bool) => DynSolValue::Bool(value),
+        (Token::Int(value), DynSolType::Int(size)) if int_fits(value, *size) => {
+            DynSolValue::Int(I256::from_raw(value.to_alloy()), *size)
+        }
+        (Token::Uint(value), DynSolType::Uint(size)) if value.bits() <= *size => {
+            DynSolValue::Uint(value.to_alloy(), *size)
+        }
+        (Token::FixedBytes(bytes), DynSolType::FixedBytes(size)) if bytes.len() == *size => {
+            DynSolValue::FixedBytes(fixed_bytes_word(bytes)?, *size)
+        }
+        (Token::Bytes(bytes), DynSolType::Bytes) => DynSolValue::Bytes(bytes),
+        (Token::String(value), DynSolType::String) => DynSolValue::String(value),
+        (Token::Array(tokens), DynSolType::Array(inner)) => DynSolValue::Array(
+            tokens
+                .into_iter()
+                .map(|token| token_to_alloy_typed(token, inner))
+                .collect::<Result<_, _>>()?,
+        ),
+        (Token::FixedArray(tokens), DynSolType::FixedArray(inner, size))
+            if tokens.len() == *size =>
+        {
+            DynSolValue::FixedArray(
+                tokens
+                    .into_iter()
+                    .map(|token| token_to_alloy_typed(token, inner))
+                    .collect::<Result<_, _>>()?,
+            )
+        }
+        (Token::Tuple(tokens), DynSolType::Tuple(types)) if tokens.len() == types.len() => {
+            DynSolValue::Tuple(
+                tokens
+                    .into_iter()
+                    .zip(types.iter())
+                    .map(|(token, ty)| token_to_alloy_typed(token, ty))
+                    .collect::<Result<_, _>>()?,
+            )
+        }
+        (token, _) => return Err(mismatch(&token)),
+    })
+}
+
+/// Whether a two's complement 256 bits value fits a signed integer of `size`
+/// bits, that is all the bits above its sign bit are copies of it
+fn int_fits(value: ethers::types::U256, size: usize) -> bool {
+    if size == 0 || size > 256 {
+        return false;
+    }
+    let shifted = I256::from_raw(value.to_alloy()).asr(size - 1);
+    shifted == I256::ZERO || shifted == I256::MINUS_ONE
+}
+
+/// Left aligns a `bytesN` value into a 32 bytes word as [DynSolValue] expects
+fn fixed_bytes_word(bytes: Vec<u8>) -> Result<B256, ConversionError> {
+    if bytes.is_empty() || bytes.len() > 32 {
+        return Err(ConversionError::InvalidFixedBytesLength(bytes.len()));
+    }
+    let mut word = B256::ZERO;
+    word[..bytes.len()].copy_from_slice(&bytes);
+    Ok(word)
+}
+
+impl ToAlloy for StateMutability {
+    type To = alloy::json_abi::StateMutability;
+
+    fn to_alloy(self) -> Self::To {
+        match self {
+            StateMutability::Pure => alloy::json_abi::StateMutability::Pure,
+            StateMutability::View => alloy::json_abi::StateMutability::View,
+            StateMutability::NonPayable => alloy::json_abi::StateMutability::NonPayable,
+            StateMutability::Payable => alloy::json_abi::StateMutability::Payable,
+        }
+    }
+}
+
+impl ToEthers for alloy::json_abi::StateMutability {
+    type To = StateMutability;
+
+    fn to_ethers(self) -> Self::To {
+        match self {
+            alloy::json_abi::StateMutability::Pure => StateMutability::Pure,
+            alloy::json_abi::StateMutability::View => StateMutability::View,
+            alloy::json_abi::StateMutability::NonPayable => StateMutability::NonPayable,
+            alloy::json_abi::StateMutability::Payable => StateMutability::Payable,
+        }
+    }
+}
+
+/// ethers tuples do not keep their components names, so the resulting
+/// components are unnamed
+impl ToAlloy for ethers::abi::Param {
+    type To = alloy::json_abi::Param;
+
+    fn to_alloy(self) -> Self::To {
+        let (ty, components) = json_abi_type(&self.kind);
+        alloy::json_abi::Param {
+            ty,
+            name: self.name,
+            components,
+            internal_type: self.internal_type.as_deref().and_then(InternalType::parse),
+        }
+    }
+}
+
+impl TryToEthers<ethers::abi::Param> for alloy::json_abi::Param {
+    fn try_to_ethers(self) -> Result<ethers::abi::Param, ConversionError> {
+        Ok(ethers::abi::Param {
+            kind: parse_param_type(&self.selector_type())?,
+            name: self.name,
+            internal_type: self.internal_type.map(|ty| ty.to_string()),
+        })
+    }
+}
+
+/// ethers event params do not carry internal types nor tuple components names
+impl ToAlloy for ethers::abi::EventParam {
+    type To = alloy::json_abi::EventParam;
+
+    fn to_alloy(self) -> Self::To {
+        let (ty, components) = json_abi_type(&self.kind);
+        alloy::json_abi::EventParam {
+            ty,
+            name: self.name,
+            indexed: self.indexed,
+            components,
+            internal_type: None,
+        }
+    }
+}
+
+impl TryToEthers<ethers::abi::EventParam> for alloy::json_abi::EventParam {
+    fn try_to_ethers(self) -> Result<ethers::abi::EventParam, ConversionError> {
+        Ok(ethers::abi::EventParam {
+            kind: parse_param_type(&self.selector_type())?,
+            name: self.name,
+            indexed: self.indexed,
+        })
+    }
+}
+
+impl ToAlloy for ethers::abi::Function {
+    type To = alloy::json_abi::Function;
+
+    fn to_alloy(self) -> Self::To {
+        alloy::json_abi::Function {
+            name: self.name,
+            inputs: self.inputs.to_alloy(),
+            outputs: self.outputs.to_alloy(),
+            state_mutability: self.state_mutability.to_alloy(),
+        }
+    }
+}
+
+impl TryToEthers<ethers::abi::Function> for alloy::json_abi::Function {
+    // the deprecated `constant` field still has to be set
+    #[allow(deprecated)]
+    fn try_to_ethers(self) -> Result<ethers::abi::Function, ConversionError> {
+        Ok(ethers::abi::Function {
+            name: self.name,
+            inputs: self.inputs.try_to_ethers()?,
+            outputs: self.outputs.try_to_ethers()?,
+            constant: None,
+            state_mutability: self.state_mutability.to_ethers(),
+        })
+    }
+}
+
+impl ToAlloy for ethers::abi::Event {
+    type To = alloy::json_abi::Event;
+
+    fn to_alloy(self) -> Self::To {
+        alloy::json_abi::Event {
+            name: self.name,
+            inputs: self.inputs.to_alloy(),
+            anonymous: self.anonymous,
+        }
+    }
+}
+
+impl TryToEthers<ethers::abi::Event> for alloy::json_abi::Event {
+    fn try_to_ethers(self) -> Result<ethers::abi::Event, ConversionError> {
+        Ok(ethers::abi::Event {
+            name: self.name,
+            inputs: self.inputs.try_to_ethers()?,
+            anonymous: self.anonymous,
+        })
+    }
+}
+
+impl ToAlloy for ethers::abi::AbiError {
+    type To = alloy::json_abi::Error;
+
+    fn to_alloy(self) -> Self::To {
+        alloy::json_abi::Error {
+            name: self.name,
+            inputs: self.inputs.to_alloy(),
+        }
+    }
+}
+
+impl TryToEthers<ethers::abi::AbiError> for alloy::json_abi::Error {
+    fn try_to_ethers(self) -> Result<ethers::abi::AbiError, ConversionError> {
+        Ok(ethers::abi::AbiError {
+            name: self.name,
+            inputs: self.inputs.try_to_ethers()?,
+        })
+    }
+}
+
+/// ethers constructors have no state mutability, they are taken as nonpayable
+impl ToAlloy for ethers::abi::Constructor {
+    type To = alloy::json_abi::Constructor;
+
+    fn to_alloy(self) -> Self::To {
+        alloy::json_abi::Constructor {
+            inputs: self.inputs.to_alloy(),
+            state_mutability: alloy::json_abi::StateMutability::NonPayable,
+        }
+    }
+}
+
+impl TryToEthers<ethers::abi::Constructor> for alloy::json_abi::Constructor {
+    fn try_to_ethers(self) -> Result<ethers::abi::Constructor, ConversionError> {
+        Ok(ethers::abi::Constructor {
+            inputs: self.inputs.try_to_ethers()?,
+        })
+    }
+}
+
+/// ethers only knows whether receive and fallback functions exist, receive is
+/// always payable and fallback is taken as nonpayable
+impl ToAlloy for Abi {
+    type To = JsonAbi;
+
+    fn to_alloy(self) -> Self::To {
+        let mut abi = JsonAbi::default();
+        abi.constructor = self.constructor.to_alloy();
+        abi.functions = self.functions.to_alloy();
+        abi.events = self.events.to_alloy();
+        abi.errors = self.errors.to_alloy();
+        abi.receive = self.receive.then_some(alloy::json_abi::Receive {
+            state_mutability: alloy::json_abi::StateMutability::Payable,
+        });
+        abi.fallback = self.fallback.then_some(alloy::json_abi::Fallback {
+            state_mutability: alloy::json_abi::StateMutability::NonPayable,
+        });
+        abi
+    }
+}
+
+impl TryToEthers<Abi> for JsonAbi {
+    fn try_to_ethers(self) -> Result<Abi, ConversionError> {
+        Ok(Abi {
+            constructor: self.constructor.try_to_ethers()?,
+            functions: self.functions.try_to_ethers()?,
+            events: self.events.try_to_ethers()?,
+            errors: self.errors.try_to_ethers()?,
+            receive: self.receive.is_some(),
+            fallback: self.fallback.is_some(),
+        })
+    }
+}
+
+/// Builds the json abi `type` and `components` of an ethers [ParamType]
+fn json_abi_type(kind: &ParamType) -> (String, Vec<alloy::json_abi::Param>) {
+    match kind {
+        ParamType::Tuple(types) => (
+            "tuple".to_string(),
+            types
+                .iter()
+                .map(|kind| {
+                    ethers::abi::Param {
+                        name: String::new(),
+                        kind: kind.clone(),
+                        internal_type: None,
+                    }
+                    .to_alloy()
+                })
+                .collect(),
+        ),
+        ParamType::Array(inner) => {
+            let (ty, components) = json_abi_type(inner);
+            (format!("{}[]", ty), components)
+        }
+        ParamType::FixedArray(inner, size) => {
+            let (ty, components) = json_abi_type(inner);
+            (format!("{}[{}]", ty, size), components)
+        }
+        kind => (kind.to_string(), vec![]),
+    }
+}
+
+/// Parses a json abi selector type such as `(address,bytes)[]` into [ParamType]
+fn parse_param_type(ty: &str) -> Result<ParamType, ConversionError> {
+    DynSolType::parse(ty)
+        .map_err(|err| ConversionError::UnsupportedAbi(err.to_string()))?
+        .try_to_ethers()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::primitives::U256;
+    use alloy::sol;
+
+    fn sample() -> (ParamType, Token) {
+        let param_type = ParamType::Tuple(vec![
+            ParamType::Address,
+            ParamType::Uint(8),
+            ParamType::Int(128),
+            ParamType::FixedBytes(4),
+            ParamType::Bytes,
+            ParamType::String,
+            ParamType::Bool,
+            ParamType::Array(Box::new(ParamType::Uint(256))),
+            ParamType::FixedArray(
+                Box::new(ParamType::Tuple(vec![ParamType::Address, ParamType::Bool])),
+                2,
+            ),
+        ]);
+        let token = Token::Tuple(vec![
+            Token::Address(ethers::types::H160::repeat_byte(0x11)),
+            Token::Uint(ethers::types::U256::from(255)),
+            Token::Int(ethers::types::I256::minus_one().into_raw()),
+            Token::FixedBytes(vec![0xde, 0xad, 0xbe, 0xef]),
+            Token::Bytes(vec![1, 2, 3]),
+            Token::String("rain".to_string()),
+            Token::Bool(true),
+            Token::Array(vec![
+                Token::Uint(ethers::types::U256::from(1)),
+                Token::Uint(ethers::types::U256::MAX),
+            ]),
+            Token::FixedArray(vec![
+                Token::Tuple(vec![
+                    Token::Address(ethers::types::H160::repeat_byte(0x22)),
+                    Token::Bool(false),
+                ]),
+                Token::Tuple(vec![
+                    Token::Address(ethers::types::H160::repeat_byte(0x33)),
+                    Token::Bool(true),
+                ]),
+            ]),
+        ]);
+        (param_type, token)
+    }
+
+    #[test]
+    fn test_param_type_round_trip() {
+        let (param_type, _) = sample();
+        let ty = param_type.clone().to_alloy();
+        assert_eq!(
+            ty.sol_type_name(),
+            "(address,uint8,int128,bytes4,bytes,string,bool,uint256[],(address,bool)[2])"
+        );
+        assert_eq!(ty.try_to_ethers().unwrap(), param_type);
+    }
+
+    #[test]
+    fn test_function_param_type() {
+        assert_eq!(
+            DynSolType::Function.try_to_ethers().unwrap(),
+            ParamType::FixedBytes(24)
+        );
+    }
+
+    #[test]
+    fn test_token_round_trip() {
+        let (_, token) = sample();
+        let value: DynSolValue = token.clone().try_to_alloy().unwrap();
+
+        let DynSolValue::Tuple(ref values) = value else {
+            panic!("expected tuple");
+        };
+        assert_eq!(values[1], DynSolValue::Uint(U256::from(255), 256));
+        assert_eq!(values[2], DynSolValue::Int(I256::MINUS_ONE, 256));
+        assert_eq!(
+            values[3],
+            DynSolValue::FixedBytes(B256::right_padding_from(&[0xde, 0xad, 0xbe, 0xef]), 4)
+        );
+
+        let Token::Tuple(ref tokens) = token else {
+            panic!("expected tuple");
+        };
+        assert_eq!(ethers::abi::encode(tokens), value.abi_encode_params());
+        assert_eq!(value.try_to_ethers().unwrap(), token);
+    }
+
+    #[test]
+    fn test_token_typed() {
+        let (param_type, token) = sample();
+        let value = ethers_token_to_alloy_typed(token.clone(), &param_type).unwrap();
+
+        assert!(param_type.to_alloy().matches(&value));
+        let DynSolValue::Tuple(ref values) = value else {
+            panic!("expected tuple");
+        };
+        assert_eq!(values[1], DynSolValue::Uint(U256::from(255), 8));
+        assert_eq!(values[2], DynSolValue::Int(I256::MINUS_ONE, 128));
+        assert_eq!(value.try_to_ethers().unwrap(), token);
+    }
+
+    #[test]
+    fn test_token_typed_mismatch() {
+        let result = ethers_token_to_alloy_typed(
+            Token::FixedBytes(vec![1, 2, 3]),
+            &ParamType::FixedBytes(4),
+        );
+        assert!(matches!(
+            result,
+            Err(ConversionError::AbiTypeMismatch { expected, .. }) if expected == "bytes4"
+        ));
+
+        let result = ethers_token_to_alloy_typed(
+            Token::Array(vec![Token::Bool(true)]),
+            &ParamType::Array(Box::new(ParamType::Address)),
+        );
+        assert!(matches!(
+            result,
+            Err(ConversionError::AbiTypeMismatch { expected, .. }) if expected == "address"
+        ));
+    }
+
+    #[test]
+    fn test_token_typed_int_overflow() {
+        let result = ethers_token_to_alloy_typed(
+            Token::Uint(ethers::types::U256::from(1000)),
+            &ParamType::Uint(8),
+        );
+        assert!(matches!(
+            result,
+            Err(ConversionError::AbiTypeMismatch { expected, .. }) if expected == "uint8"
+        ));
+        let value = ethers_token_to_alloy_typed(
+            Token::Uint(ethers::types::U256::from(255)),
+            &ParamType::Uint(8),
+        )
+        .unwrap();
+        assert_eq!(value, DynSolValue::Uint(U256::from(255), 8));
+
+        // int8 ranges from -128 to 127
+        let int8 = |value: I256| Token::Int(value.into_raw().to_ethers());
+        for value in [I256::try_from(-128).unwrap(), I256::try_from(127).unwrap()] {
+            let result = ethers_token_to_alloy_typed(int8(value), &ParamType::Int(8)).unwrap();
+            assert_eq!(result, DynSolValue::Int(value, 8));
+        }
+        for value in [I256::try_from(-129).unwrap(), I256::try_from(128).unwrap()] {
+            let result = ethers_token_to_alloy_typed(int8(value), &ParamType::Int(8));
+            assert!(matches!(
+                result,
+                Err(ConversionError::AbiTypeMismatch { expected, .. }) if expected == "int8"
+            ));
+        }
+    }
+
+    // the abi of the bundled Multicall3 interface, as generated by `sol!` from
+    // the same source the multicall module uses
+    sol!(
+        #[sol(abi)]
+        "./contracts/IMulticall3.sol"
+    );
+
+    #[test]
+    fn test_abi_round_trip() {
+        let expected: JsonAbi = IMulticall3::abi::contract();
+        let json = serde_json::to_string(&expected).unwrap();
+        let ethers_abi: Abi = serde_json::from_str(&json).unwrap();
+        assert!(!expected.functions.is_empty());
+
+        let alloy_abi = ethers_abi.clone().to_alloy();
+        assert_eq!(alloy_abi.functions.len(), expected.functions.len());
+        for (function, expected) in alloy_abi.functions().zip(expected.functions()) {
+            assert_eq!(function.name, expected.name);
+            assert_eq!(function.signature(), expected.signature());
+            assert_eq!(function.selector(), expected.selector());
+            assert_eq!(function.state_mutability, expected.state_mutability);
+            for (param, expected) in function
+                .inputs
+                .iter()
+                .chain(function.outputs.iter())
+                .zip(expected.inputs.iter().chain(expected.outputs.iter()))
+            {
+                assert_eq!(param.name, expected.name);
+                assert_eq!(param.selector_type(), expected.selector_type());
+                assert_eq!(param.internal_type, expected.internal_type);
+            }
+        }
+
+        assert_eq!(alloy_abi.try_to_ethers().unwrap(), ethers_abi);
+        assert_eq!(expected.try_to_ethers().unwrap(), ethers_abi);
+    }
+
+    #[test]
+    fn test_abi_receive_fallback_constructor() {
+        let abi = Abi {
+            constructor: Some(ethers::abi::Constructor {
+                inputs: vec![ethers::abi::Param {
+                    name: "owner".to_string(),
+                    kind: ParamType::Address,
+                    internal_type: Some("address".to_string()),
+                }],
+            }),
+            functions: Default::default(),
+            events: Default::default(),
+            errors: Default::default(),
+            receive: true,
+            fallback: true,
+        };
+
+        let alloy_abi = abi.clone().to_alloy();
+        assert_eq!(
+            alloy_abi.receive.as_ref().unwrap().state_mutability,
+            alloy::json_abi::StateMutability::Payable
+        );
+        assert!(alloy_abi.fallback.is_some());
+        assert_eq!(
+            alloy_abi.constructor.as_ref().unwrap().inputs[0].ty,
+            "address"
+        );
+        assert_eq!(alloy_abi.try_to_ethers().unwrap(), abi);
+    }
+
+    #[test]
+    fn test_event_and_error() {
+        let event = alloy::json_abi::Event::parse(
+            "event Transfer(address indexed from, address indexed to, uint256 value)",
+        )
+        .unwrap();
+        let ethers_event: ethers::abi::Event = event.clone().try_to_ethers().unwrap();
+        assert_eq!(ethers_event.name, "Transfer");
+        assert_eq!(
+            ethers_event.inputs,
+            vec![
+                ethers::abi::EventParam {
+                    name: "from".to_string(),
+                    kind: ParamType::Address,
+                    indexed: true,
+                },
+                ethers::abi::EventParam {
+                    name: "to".to_string(),
+                    kind: ParamType::Address,
+                    indexed: true,
+                },
+                ethers::abi::EventParam {
+                    name: "value".to_string(),
+                    kind: ParamType::Uint(256),
+                    indexed: false,
+                },
+            ]
+        );
+        assert_eq!(
+            ethers_event.signature().as_bytes(),
+            event.selector().as_slice()
+        );
+        assert_eq!(ethers_event.to_alloy(), event);
+
+        let error = alloy::json_abi::Error::parse("error Unauthorized(address caller)").unwrap();
+        let ethers_error: ethers::abi::AbiError = error.clone().try_to_ethers().unwrap();
+        assert_eq!(ethers_error.name, "Unauthorized");
+        assert_eq!(ethers_error.inputs[0].kind, ParamType::Address);
+        assert_eq!(ethers_error.to_alloy(), error);
+    }
+
+    #[test]
+    fn test_invalid_fixed_bytes() {
+        let result: Result<DynSolValue, _> = Token::FixedBytes(vec![0; 33]).try_to_alloy();
+        assert_eq!(result, Err(ConversionError::InvalidFixedBytesLength(33)));
+    }
+}
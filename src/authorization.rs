@@ -0,0 +1,314 @@
+use crate::convert::{ConversionError, ToEthers, TryToAlloy};
+use crate::request_shim::AlloyTransactionRequest;
+use alloy::primitives::{keccak256, Address, Bytes, Signature, B256, U256, U64};
+use ethers::core::k256::ecdsa::signature::hazmat::PrehashSigner;
+use ethers::core::k256::ecdsa::{RecoveryId, Signature as RecoverableSignature};
+use ethers::signers::Wallet;
+use ethers::utils::rlp::RlpStream;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// Prefix of the EIP-7702 authorization signing payload
+pub const EIP7702_AUTHORIZATION_MAGIC: u8 = 0x05;
+
+/// EIP-2718 type of EIP-7702 set code transactions
+pub const EIP7702_TX_TYPE: u8 = 0x04;
+
+#[derive(Error, Debug)]
+pub enum AuthorizationError {
+    #[error("failed to sign: {0}")]
+    SignError(String),
+    #[error(transparent)]
+    ConversionError(#[from] ConversionError),
+}
+
+/// Signers able to sign a raw 32 byte hash, which EIP-7702 authorizations and
+/// transactions need but the ethers [Signer](ethers::signers::Signer) trait
+/// does not expose
+pub trait HashSigner {
+    fn sign_prehash(&self, hash: B256) -> Result<ethers::types::Signature, AuthorizationError>;
+}
+
+/// Any ethers wallet backed by a prehash signing key, such as
+/// [LocalWallet](ethers::signers::LocalWallet)
+impl<D> HashSigner for Wallet<D>
+where
+    D: PrehashSigner<(RecoverableSignature, RecoveryId)>,
+{
+    fn sign_prehash(&self, hash: B256) -> Result<ethers::types::Signature, AuthorizationError> {
+        self.sign_hash(hash.to_ethers())
+            .map_err(|err| AuthorizationError::SignError(err.to_string()))
+    }
+}
+
+/// An EIP-7702 authorization for an account to delegate its code to `address`,
+/// a chain id of zero makes it valid on any chain
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Authorization {
+    pub chain_id: U256,
+    pub address: Address,
+    pub nonce: U64,
+}
+
+impl Authorization {
+    /// Hash the authorizing account signs, `keccak256(MAGIC || rlp([chain_id, address, nonce]))`
+    pub fn signature_hash(&self) -> B256 {
+        let mut stream = RlpStream::new_list(3);
+        stream.append(&self.chain_id.to_ethers());
+        stream.append(&self.address.to_ethers());
+        stream.append(&self.nonce.to_ethers());
+
+        let mut payload = vec![EIP7702_AUTHORIZATION_MAGIC];
+        payload.extend_from_slice(&stream.out());
+        keccak256(payload)
+    }
+
+    pub fn sign<S: HashSigner>(
+        self,
+        signer: &S,
+    ) -> Result<SignedAuthorization, AuthorizationError> {
+        let signature: Signature = signer.sign_prehash(self.signature_hash())?.try_to_alloy()?;
+        Ok(self.into_signed(signature))
+    }
+
+    pub fn into_signed(self, signature: Signature) -> SignedAuthorization {
+        SignedAuthorization {
+            inner: self,
+            y_parity: U64::from(signature.v().y_parity_byte()),
+            r: signature.r(),
+            s: signature.s(),
+        }
+    }
+}
+
+/// An EIP-7702 authorization with the signature of the authorizing account
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SignedAuthorization {
+    #[serde(flatten)]
+    pub inner: Authorization,
+    pub y_parity: U64,
+    pub r: U256,
+    pub s: U256,
+}
+
+impl SignedAuthorization {
+    pub fn signature(&self) -> Result<Signature, ConversionError> {
+        Signature::from_rs_and_parity(self.r, self.s, self.y_parity.to::<u64>())
+            .map_err(|err| ConversionError::InvalidSignature(err.to_string()))
+    }
+
+    /// Recovers the account that signed the authorization
+    pub fn recover_authority(&self) -> Result<Address, ConversionError> {
+        self.signature()?
+            .recover_address_from_prehash(&self.inner.signature_hash())
+            .map_err(|err| ConversionError::InvalidSignature(err.to_string()))
+    }
+
+    fn rlp_append(&self, stream: &mut RlpStream) {
+        stream.begin_list(6);
+        stream.append(&self.inner.chain_id.to_ethers());
+        stream.append(&self.inner.address.to_ethers());
+        stream.append(&self.inner.nonce.to_ethers());
+        stream.append(&self.y_parity.to_ethers());
+        stream.append(&self.r.to_ethers());
+        stream.append(&self.s.to_ethers());
+    }
+}
+
+fn required<T: Clone>(value: &Option<T>, field: &str) -> Result<T, ConversionError> {
+    value
+        .clone()
+        .ok_or(ConversionError::MissingField(field.to_string()))
+}
+
+impl AlloyTransactionRequest {
+    /// Appends the unsigned fields of the EIP-7702 transaction, which cannot
+    /// create contracts and needs a non empty authorization list
+    fn rlp_append_eip7702_fields(&self, stream: &mut RlpStream) -> Result<(), ConversionError> {
+        let authorization_list = required(&self.authorization_list, "authorization_list")?;
+        if authorization_list.is_empty() {
+            return Err(ConversionError::MissingField(
+                "authorization_list".to_string(),
+            ));
+        }
+
+        stream.append(&required(&self.chain_id, "chain_id")?.to_ethers());
+        stream.append(&required(&self.nonce, "nonce")?.to_ethers());
+        stream.append(
+            &required(&self.max_priority_fee_per_gas, "max_priority_fee_per_gas")?.to_ethers(),
+        );
+        stream.append(&required(&self.max_fee_per_gas, "max_fee_per_gas")?.to_ethers());
+        stream.append(&required(&self.gas, "gas")?.to_ethers());
+        stream.append(&required(&self.to, "to")?.to_ethers());
+        stream.append(&self.value.unwrap_or_default().to_ethers());
+        stream.append(&self.data.clone().unwrap_or_default().to_ethers());
+        stream.append(&self.access_list.clone().unwrap_or_default().to_ethers());
+        stream.begin_list(authorization_list.len());
+        for authorization in &authorization_list {
+            authorization.rlp_append(stream);
+        }
+        Ok(())
+    }
+
+    /// Hash the sender signs, `keccak256(0x04 || rlp([chain_id, ..., authorization_list]))`
+    pub fn eip7702_signature_hash(&self) -> Result<B256, ConversionError> {
+        let mut stream = RlpStream::new_list(10);
+        self.rlp_append_eip7702_fields(&mut stream)?;
+
+        let mut payload = vec![EIP7702_TX_TYPE];
+        payload.extend_from_slice(&stream.out());
+        Ok(keccak256(payload))
+    }
+
+    /// EIP-2718 encoding of the signed EIP-7702 transaction, ready for
+    /// `eth_sendRawTransaction`
+    pub fn eip7702_rlp_signed(&self, signature: &Signature) -> Result<Bytes, ConversionError> {
+        let mut stream = RlpStream::new_list(13);
+        self.rlp_append_eip7702_fields(&mut stream)?;
+        stream.append(&signature.v().y_parity_byte());
+        stream.append(&signature.r().to_ethers());
+        stream.append(&signature.s().to_ethers());
+
+        let mut encoded = vec![EIP7702_TX_TYPE];
+        encoded.extend_from_slice(&stream.out());
+        Ok(encoded.into())
+    }
+
+    /// Signs the EIP-7702 transaction, returning its EIP-2718 encoding
+    pub fn sign_eip7702<S: HashSigner>(&self, signer: &S) -> Result<Bytes, AuthorizationError> {
+        let signature: Signature = signer
+            .sign_prehash(self.eip7702_signature_hash()?)?
+            .try_to_alloy()?;
+        Ok(self.eip7702_rlp_signed(&signature)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ToAlloy;
+    use ethers::core::rand::thread_rng;
+    use ethers::signers::{LocalWallet, Signer};
+    use ethers::utils::rlp::Rlp;
+
+    fn authorization() -> Authorization {
+        Authorization {
+            chain_id: U256::from(1),
+            address: Address::repeat_byte(0x77),
+            nonce: U64::from(3),
+        }
+    }
+
+    fn request(authorization_list: Vec<SignedAuthorization>) -> AlloyTransactionRequest {
+        AlloyTransactionRequest::new()
+            .with_to(Some(Address::repeat_byte(0x22)))
+            .with_gas(Some(U256::from(100000)))
+            .with_max_fee_per_gas(Some(U256::from(200)))
+            .with_max_priority_fee_per_gas(Some(U256::from(100)))
+            .with_nonce(Some(U256::from(7)))
+            .with_chain_id(Some(U64::from(1)))
+            .with_data(Some(vec![1, 2, 3]))
+            .with_authorization_list(Some(authorization_list))
+    }
+
+    #[test]
+    fn test_sign_authorization() {
+        let wallet = LocalWallet::new(&mut thread_rng());
+        let signed = authorization().sign(&wallet).unwrap();
+
+        assert_eq!(signed.inner, authorization());
+        assert!(signed.y_parity <= U64::from(1));
+        assert_eq!(
+            signed.recover_authority().unwrap(),
+            wallet.address().to_alloy()
+        );
+
+        // a different authorization does not recover to the signer
+        let tampered = SignedAuthorization {
+            inner: Authorization {
+                nonce: U64::from(4),
+                ..authorization()
+            },
+            ..signed
+        };
+        assert_ne!(
+            tampered.recover_authority().unwrap(),
+            wallet.address().to_alloy()
+        );
+    }
+
+    #[test]
+    fn test_authorization_serde() {
+        let signed = authorization().into_signed(
+            Signature::from_rs_and_parity(U256::from(1), U256::from(2), 1_u64).unwrap(),
+        );
+        let json = serde_json::to_string(&signed).unwrap();
+        assert_eq!(
+            json,
+            r#"{"chainId":"0x1","address":"0x7777777777777777777777777777777777777777","nonce":"0x3","yParity":"0x1","r":"0x1","s":"0x2"}"#
+        );
+        assert_eq!(
+            serde_json::from_str::<SignedAuthorization>(&json).unwrap(),
+            signed
+        );
+    }
+
+    #[test]
+    fn test_sign_eip7702() {
+        let authority = LocalWallet::new(&mut thread_rng());
+        let sender = LocalWallet::new(&mut thread_rng());
+        let request = request(vec![authorization().sign(&authority).unwrap()]);
+
+        let encoded = request.sign_eip7702(&sender).unwrap();
+        assert_eq!(encoded[0], EIP7702_TX_TYPE);
+
+        let rlp = Rlp::new(&encoded[1..]);
+        assert_eq!(rlp.item_count().unwrap(), 13);
+        assert_eq!(rlp.val_at::<u64>(0).unwrap(), 1);
+        assert_eq!(rlp.val_at::<u64>(1).unwrap(), 7);
+        assert_eq!(
+            rlp.val_at::<ethers::types::H160>(5).unwrap(),
+            ethers::types::H160::repeat_byte(0x22)
+        );
+        assert_eq!(rlp.val_at::<Vec<u8>>(7).unwrap(), vec![1, 2, 3]);
+        let authorization_list = rlp.at(9).unwrap();
+        assert_eq!(authorization_list.item_count().unwrap(), 1);
+        assert_eq!(authorization_list.at(0).unwrap().item_count().unwrap(), 6);
+
+        // the sender is recovered from the signature hash
+        let signature = Signature::from_rs_and_parity(
+            rlp.val_at::<ethers::types::U256>(11).unwrap().to_alloy(),
+            rlp.val_at::<ethers::types::U256>(12).unwrap().to_alloy(),
+            rlp.val_at::<u64>(10).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            signature
+                .recover_address_from_prehash(&request.eip7702_signature_hash().unwrap())
+                .unwrap(),
+            sender.address().to_alloy()
+        );
+    }
+
+    #[test]
+    fn test_eip7702_missing_fields() {
+        assert_eq!(
+            request(vec![]).eip7702_signature_hash().unwrap_err(),
+            ConversionError::MissingField("authorization_list".to_string())
+        );
+
+        let signed = authorization().into_signed(
+            Signature::from_rs_and_parity(U256::from(1), U256::from(2), 1_u64).unwrap(),
+        );
+        let request = AlloyTransactionRequest {
+            to: None,
+            ..request(vec![signed])
+        };
+        assert_eq!(
+            request.eip7702_signature_hash().unwrap_err(),
+            ConversionError::MissingField("to".to_string())
+        );
+    }
+}
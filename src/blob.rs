@@ -0,0 +1,91 @@
+use alloy::eips::eip4844::{kzg_to_versioned_hash, Blob, BlobTransactionSidecar, Bytes48};
+use alloy::primitives::B256;
+
+/// Builds a blob sidecar from blobs with their already computed KZG
+/// commitments and proofs
+#[derive(Clone, Default, PartialEq, Eq, Debug)]
+pub struct BlobSidecarBuilder {
+    blobs: Vec<Blob>,
+    commitments: Vec<Bytes48>,
+    proofs: Vec<Bytes48>,
+}
+
+impl BlobSidecarBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a blob with its KZG commitment and proof
+    pub fn with_blob(mut self, blob: Blob, commitment: Bytes48, proof: Bytes48) -> Self {
+        self.blobs.push(blob);
+        self.commitments.push(commitment);
+        self.proofs.push(proof);
+        self
+    }
+
+    /// Versioned hashes of the added blobs, in the order they were added
+    pub fn versioned_hashes(&self) -> Vec<B256> {
+        self.commitments
+            .iter()
+            .map(|commitment| kzg_to_versioned_hash(commitment.as_slice()))
+            .collect()
+    }
+
+    pub fn build(self) -> BlobTransactionSidecar {
+        BlobTransactionSidecar::new(self.blobs, self.commitments, self.proofs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::eips::eip4844::VERSIONED_HASH_VERSION_KZG;
+    use alloy::primitives::b256;
+
+    // commitment of the blob of zeroes, the point at infinity
+    fn empty_blob_commitment() -> Bytes48 {
+        let mut commitment = Bytes48::ZERO;
+        commitment[0] = 0xc0;
+        commitment
+    }
+
+    #[test]
+    fn test_versioned_hashes() {
+        let builder = BlobSidecarBuilder::new().with_blob(
+            Blob::ZERO,
+            empty_blob_commitment(),
+            empty_blob_commitment(),
+        );
+        assert_eq!(
+            builder.versioned_hashes(),
+            vec![b256!(
+                "010657f37554c781402a22917dee2f75def7ab966d7b770905398eba3c444014"
+            )]
+        );
+    }
+
+    #[test]
+    fn test_sidecar_builder() {
+        let builder = BlobSidecarBuilder::new()
+            .with_blob(Blob::ZERO, empty_blob_commitment(), empty_blob_commitment())
+            .with_blob(Blob::ZERO, Bytes48::repeat_byte(1), Bytes48::repeat_byte(2));
+
+        let versioned_hashes = builder.versioned_hashes();
+        assert_eq!(versioned_hashes.len(), 2);
+        assert!(versioned_hashes
+            .iter()
+            .all(|hash| hash[0] == VERSIONED_HASH_VERSION_KZG));
+
+        let sidecar = builder.build();
+        assert_eq!(sidecar.blobs.len(), 2);
+        assert_eq!(
+            sidecar.commitments,
+            vec![empty_blob_commitment(), Bytes48::repeat_byte(1)]
+        );
+        assert_eq!(sidecar.proofs[1], Bytes48::repeat_byte(2));
+        assert_eq!(
+            sidecar.versioned_hashes().collect::<Vec<_>>(),
+            versioned_hashes
+        );
+    }
+}
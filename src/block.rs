@@ -0,0 +1,538 @@
+use crate::convert::{ConversionError, ToAlloy, ToEthers, TryToAlloy, TryToEthers};
+use alloy::primitives::{Bloom, U256};
+use alloy::rpc::types::{
+    BlockId, BlockNumberOrTag, BlockTransactions, Header, Parity, Signature, Withdrawal,
+};
+use ethers::types::{Block, BlockNumber, Transaction, TxHash};
+use serde::de::DeserializeOwned;
+use serde_json::json;
+
+impl ToAlloy for BlockNumber {
+    type To = BlockNumberOrTag;
+
+    fn to_alloy(self) -> Self::To {
+        match self {
+            BlockNumber::Latest => BlockNumberOrTag::Latest,
+            BlockNumber::Finalized => BlockNumberOrTag::Finalized,
+            BlockNumber::Safe => BlockNumberOrTag::Safe,
+            BlockNumber::Earliest => BlockNumberOrTag::Earliest,
+            BlockNumber::Pending => BlockNumberOrTag::Pending,
+            BlockNumber::Number(number) => BlockNumberOrTag::Number(number.as_u64()),
+        }
+    }
+}
+
+impl ToEthers for BlockNumberOrTag {
+    type To = BlockNumber;
+
+    fn to_ethers(self) -> Self::To {
+        match self {
+            BlockNumberOrTag::Latest => BlockNumber::Latest,
+            BlockNumberOrTag::Finalized => BlockNumber::Finalized,
+            BlockNumberOrTag::Safe => BlockNumber::Safe,
+            BlockNumberOrTag::Earliest => BlockNumber::Earliest,
+            BlockNumberOrTag::Pending => BlockNumber::Pending,
+            BlockNumberOrTag::Number(number) => BlockNumber::Number(number.into()),
+        }
+    }
+}
+
+impl ToAlloy for ethers::types::BlockId {
+    type To = BlockId;
+
+    fn to_alloy(self) -> Self::To {
+        match self {
+            ethers::types::BlockId::Hash(hash) => BlockId::from(hash.to_alloy()),
+            ethers::types::BlockId::Number(number) => BlockId::Number(number.to_alloy()),
+        }
+    }
+}
+
+/// ethers block ids have no notion of EIP-1898 `requireCanonical`, it is dropped
+impl ToEthers for BlockId {
+    type To = ethers::types::BlockId;
+
+    fn to_ethers(self) -> Self::To {
+        match self {
+            BlockId::Hash(hash) => ethers::types::BlockId::Hash(hash.block_hash.to_ethers()),
+            BlockId::Number(number) => ethers::types::BlockId::Number(number.to_ethers()),
+        }
+    }
+}
+
+impl TryToAlloy<Withdrawal> for ethers::types::Withdrawal {
+    fn try_to_alloy(self) -> Result<Withdrawal, ConversionError> {
+        Ok(Withdrawal {
+            index: self.index.as_u64(),
+            validator_index: self.validator_index.as_u64(),
+            address: self.address.to_alloy(),
+            amount: self.amount.try_to_alloy()?,
+        })
+    }
+}
+
+impl ToEthers for Withdrawal {
+    type To = ethers::types::Withdrawal;
+
+    fn to_ethers(self) -> Self::To {
+        ethers::types::Withdrawal {
+            index: self.index.into(),
+            validator_index: self.validator_index.into(),
+            address: self.address.to_ethers(),
+            amount: self.amount.into(),
+        }
+    }
+}
+
+// ethers transactions have no fields for the y parity and the EIP-4844 blob
+// fields, they end up in the unknown fields and are moved to and from the
+// typed alloy fields so that they are not duplicated
+
+const Y_PARITY: &str = "yParity";
+const MAX_FEE_PER_BLOB_GAS: &str = "maxFeePerBlobGas";
+const BLOB_VERSIONED_HASHES: &str = "blobVersionedHashes";
+
+fn take_other_field<T: DeserializeOwned>(
+    other: &mut ethers::types::OtherFields,
+    key: &str,
+) -> Result<Option<T>, ConversionError> {
+    other
+        .remove(key)
+        .map(serde_json::from_value)
+        .transpose()
+        .map_err(|err| ConversionError::Json(format!("{}: {}", key, err)))
+}
+
+/// Transactions with a type above 255 are unsupported
+impl TryToAlloy<alloy::rpc::types::Transaction> for Transaction {
+    fn try_to_alloy(self) -> Result<alloy::rpc::types::Transaction, ConversionError> {
+        let mut other = self.other;
+        let y_parity: Option<alloy::primitives::U64> = take_other_field(&mut other, Y_PARITY)?;
+        let max_fee_per_blob_gas: Option<alloy::primitives::U128> =
+            take_other_field(&mut other, MAX_FEE_PER_BLOB_GAS)?;
+        let blob_versioned_hashes = take_other_field(&mut other, BLOB_VERSIONED_HASHES)?;
+
+        let transaction_type = self
+            .transaction_type
+            .map(|ty| {
+                u8::try_from(ty.as_u64())
+                    .map_err(|_| ConversionError::UnsupportedTransactionType(ty.as_u64()))
+            })
+            .transpose()?;
+
+        let mut transaction = alloy::rpc::types::Transaction {
+            hash: self.hash.to_alloy(),
+            nonce: self.nonce.try_to_alloy()?,
+            block_hash: self.block_hash.to_alloy(),
+            block_number: self.block_number.map(|number| number.as_u64()),
+            transaction_index: self.transaction_index.map(|index| index.as_u64()),
+            from: self.from.to_alloy(),
+            to: self.to.to_alloy(),
+            value: self.value.to_alloy(),
+            gas_price: self.gas_price.try_to_alloy()?,
+            gas: self.gas.try_to_alloy()?,
+            max_fee_per_gas: self.max_fee_per_gas.try_to_alloy()?,
+            max_priority_fee_per_gas: self.max_priority_fee_per_gas.try_to_alloy()?,
+            max_fee_per_blob_gas: max_fee_per_blob_gas.map(|value| value.to()),
+            input: self.input.to_alloy(),
+            signature: Some(Signature {
+                r: self.r.to_alloy(),
+                s: self.s.to_alloy(),
+                v: U256::from(self.v.as_u64()),
+                y_parity: y_parity.map(|y_parity| Parity(!y_parity.is_zero())),
+            }),
+            chain_id: self.chain_id.try_to_alloy()?,
+            blob_versioned_hashes,
+            access_list: self.access_list.to_alloy(),
+            transaction_type,
+            ..Default::default()
+        };
+        transaction.other.extend(
+            other
+                .iter()
+                .map(|(key, value)| (key.clone(), value.clone())),
+        );
+        Ok(transaction)
+    }
+}
+
+/// A missing signature is taken as a zero one
+impl TryToEthers<Transaction> for alloy::rpc::types::Transaction {
+    fn try_to_ethers(self) -> Result<Transaction, ConversionError> {
+        let signature = self.signature.unwrap_or(Signature {
+            r: U256::ZERO,
+            s: U256::ZERO,
+            v: U256::ZERO,
+            y_parity: None,
+        });
+
+        let mut other = ethers::types::OtherFields::default();
+        other.extend(
+            self.other
+                .iter()
+                .map(|(key, value)| (key.clone(), value.clone())),
+        );
+        if let Some(y_parity) = signature.y_parity {
+            other.insert(
+                Y_PARITY.to_string(),
+                json!(format!("{:#x}", u8::from(y_parity.0))),
+            );
+        }
+        if let Some(max_fee_per_blob_gas) = self.max_fee_per_blob_gas {
+            other.insert(
+                MAX_FEE_PER_BLOB_GAS.to_string(),
+                json!(format!("{:#x}", max_fee_per_blob_gas)),
+            );
+        }
+        if let Some(blob_versioned_hashes) = self.blob_versioned_hashes {
+            other.insert(
+                BLOB_VERSIONED_HASHES.to_string(),
+                json!(blob_versioned_hashes),
+            );
+        }
+
+        Ok(Transaction {
+            hash: self.hash.to_ethers(),
+            nonce: self.nonce.into(),
+            block_hash: self.block_hash.to_ethers(),
+            block_number: self.block_number.map(Into::into),
+            transaction_index: self.transaction_index.map(Into::into),
+            from: self.from.to_ethers(),
+            to: self.to.to_ethers(),
+            value: self.value.to_ethers(),
+            gas_price: self.gas_price.map(Into::into),
+            gas: self.gas.into(),
+            input: self.input.to_ethers(),
+            v: u64::try_from(signature.v)
+                .map_err(|_| ConversionError::Overflow {
+                    from_bits: 256,
+                    to_bits: 64,
+                })?
+                .into(),
+            r: signature.r.to_ethers(),
+            s: signature.s.to_ethers(),
+            transaction_type: self.transaction_type.map(|ty| u64::from(ty).into()),
+            access_list: self.access_list.to_ethers(),
+            max_priority_fee_per_gas: self.max_priority_fee_per_gas.map(Into::into),
+            max_fee_per_gas: self.max_fee_per_gas.map(Into::into),
+            chain_id: self.chain_id.map(Into::into),
+            other,
+        })
+    }
+}
+
+/// Converts the header and body of a block, the transactions are converted by
+/// the caller since they differ between hashes and full objects
+fn block_to_alloy<TX>(
+    block: Block<TX>,
+    transactions: impl FnOnce(Vec<TX>) -> Result<BlockTransactions, ConversionError>,
+) -> Result<alloy::rpc::types::Block, ConversionError> {
+    let header = Header {
+        hash: block.hash.to_alloy(),
+        parent_hash: block.parent_hash.to_alloy(),
+        uncles_hash: block.uncles_hash.to_alloy(),
+        miner: block.author.unwrap_or_default().to_alloy(),
+        state_root: block.state_root.to_alloy(),
+        transactions_root: block.transactions_root.to_alloy(),
+        receipts_root: block.receipts_root.to_alloy(),
+        logs_bloom: Bloom::from(block.logs_bloom.unwrap_or_default().0),
+        difficulty: block.difficulty.to_alloy(),
+        number: block.number.map(|number| number.as_u64()),
+        gas_limit: block.gas_limit.try_to_alloy()?,
+        gas_used: block.gas_used.try_to_alloy()?,
+        timestamp: block.timestamp.try_to_alloy()?,
+        total_difficulty: block.total_difficulty.to_alloy(),
+        extra_data: block.extra_data.to_alloy(),
+        mix_hash: block.mix_hash.to_alloy(),
+        nonce: block.nonce.to_alloy(),
+        base_fee_per_gas: block.base_fee_per_gas.try_to_alloy()?,
+        withdrawals_root: block.withdrawals_root.to_alloy(),
+        blob_gas_used: block.blob_gas_used.try_to_alloy()?,
+        excess_blob_gas: block.excess_blob_gas.try_to_alloy()?,
+        parent_beacon_block_root: block.parent_beacon_block_root.to_alloy(),
+        ..Default::default()
+    };
+
+    let mut alloy_block = alloy::rpc::types::Block {
+        header,
+        uncles: block.uncles.to_alloy(),
+        transactions: transactions(block.transactions)?,
+        size: block.size.to_alloy(),
+        withdrawals: block.withdrawals.try_to_alloy()?,
+        ..Default::default()
+    };
+    alloy_block.other.extend(
+        block
+            .other
+            .iter()
+            .map(|(key, value)| (key.clone(), value.clone())),
+    );
+    Ok(alloy_block)
+}
+
+/// Converts the header and body of a block around the already converted
+/// transactions, ethers seal fields are left empty
+fn block_to_ethers<TX: Default>(
+    block: alloy::rpc::types::Block,
+    transactions: Vec<TX>,
+) -> Block<TX> {
+    let header = block.header;
+    let mut ethers_block = Block {
+        hash: header.hash.to_ethers(),
+        parent_hash: header.parent_hash.to_ethers(),
+        uncles_hash: header.uncles_hash.to_ethers(),
+        author: Some(header.miner.to_ethers()),
+        state_root: header.state_root.to_ethers(),
+        transactions_root: header.transactions_root.to_ethers(),
+        receipts_root: header.receipts_root.to_ethers(),
+        number: header.number.map(Into::into),
+        gas_used: header.gas_used.into(),
+        gas_limit: header.gas_limit.into(),
+        extra_data: header.extra_data.to_ethers(),
+        logs_bloom: Some(ethers::types::Bloom(header.logs_bloom.0 .0)),
+        timestamp: header.timestamp.into(),
+        difficulty: header.difficulty.to_ethers(),
+        total_difficulty: header.total_difficulty.to_ethers(),
+        uncles: block.uncles.to_ethers(),
+        transactions,
+        size: block.size.to_ethers(),
+        mix_hash: header.mix_hash.to_ethers(),
+        nonce: header.nonce.to_ethers(),
+        base_fee_per_gas: header.base_fee_per_gas.map(Into::into),
+        blob_gas_used: header.blob_gas_used.map(Into::into),
+        excess_blob_gas: header.excess_blob_gas.map(Into::into),
+        withdrawals_root: header.withdrawals_root.to_ethers(),
+        withdrawals: block.withdrawals.to_ethers(),
+        parent_beacon_block_root: header.parent_beacon_block_root.to_ethers(),
+        ..Default::default()
+    };
+    ethers_block.other.extend(
+        block
+            .other
+            .iter()
+            .map(|(key, value)| (key.clone(), value.clone())),
+    );
+    ethers_block
+}
+
+/// Converts a block with transaction hashes only
+impl TryToAlloy<alloy::rpc::types::Block> for Block<TxHash> {
+    fn try_to_alloy(self) -> Result<alloy::rpc::types::Block, ConversionError> {
+        block_to_alloy(self, |hashes| {
+            Ok(BlockTransactions::Hashes(hashes.to_alloy()))
+        })
+    }
+}
+
+/// Converts a block with full transaction objects
+impl TryToAlloy<alloy::rpc::types::Block> for Block<Transaction> {
+    fn try_to_alloy(self) -> Result<alloy::rpc::types::Block, ConversionError> {
+        block_to_alloy(self, |transactions| {
+            Ok(BlockTransactions::Full(transactions.try_to_alloy()?))
+        })
+    }
+}
+
+/// Errors if the block holds full transaction objects
+impl TryToEthers<Block<TxHash>> for alloy::rpc::types::Block {
+    fn try_to_ethers(mut self) -> Result<Block<TxHash>, ConversionError> {
+        let hashes = match std::mem::take(&mut self.transactions) {
+            BlockTransactions::Hashes(hashes) => hashes.to_ethers(),
+            BlockTransactions::Uncle => vec![],
+            BlockTransactions::Full(_) => {
+                return Err(ConversionError::BlockTransactionsMismatch(
+                    "transaction hashes".to_string(),
+                ))
+            }
+        };
+        Ok(block_to_ethers(self, hashes))
+    }
+}
+
+/// Errors if the block only holds transaction hashes
+impl TryToEthers<Block<Transaction>> for alloy::rpc::types::Block {
+    fn try_to_ethers(mut self) -> Result<Block<Transaction>, ConversionError> {
+        let transactions = match std::mem::take(&mut self.transactions) {
+            BlockTransactions::Full(transactions) => transactions.try_to_ethers()?,
+            BlockTransactions::Hashes(hashes) if hashes.is_empty() => vec![],
+            BlockTransactions::Uncle => vec![],
+            BlockTransactions::Hashes(_) => {
+                return Err(ConversionError::BlockTransactionsMismatch(
+                    "full transactions".to_string(),
+                ))
+            }
+        };
+        Ok(block_to_ethers(self, transactions))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::primitives::{Address, B256};
+    use ethers::types::{H256, U64};
+
+    // block 0 of ethereum mainnet, as returned by `eth_getBlockByNumber`
+    const GENESIS_BLOCK: &str = include_str!("../fixtures/genesis_block.json");
+    // a post london block holding one legacy, one EIP-2930 and one EIP-1559
+    // transaction signed with known test keys, the transaction hashes and
+    // signatures and the block hash follow from their encodings while the
+    // state and receipts roots are arbitrary
+    const BLOCK_WITH_TRANSACTIONS: &str = include_str!("../fixtures/block_with_transactions.json");
+
+    #[test]
+    fn test_block_number_round_trip() {
+        let numbers = [
+            BlockNumber::Latest,
+            BlockNumber::Finalized,
+            BlockNumber::Safe,
+            BlockNumber::Earliest,
+            BlockNumber::Pending,
+            BlockNumber::Number(U64::from(18_000_000)),
+        ];
+        for number in numbers {
+            assert_eq!(number.to_alloy().to_ethers(), number);
+        }
+        assert_eq!(
+            BlockNumber::Number(U64::from(18_000_000)).to_alloy(),
+            BlockNumberOrTag::Number(18_000_000)
+        );
+    }
+
+    #[test]
+    fn test_block_id_round_trip() {
+        let hash = H256::random();
+        let block_id = ethers::types::BlockId::Hash(hash);
+        let alloy_block_id = block_id.to_alloy();
+        assert_eq!(alloy_block_id, BlockId::from(B256::from(hash.0)));
+        assert_eq!(alloy_block_id.to_ethers(), block_id);
+
+        let block_id = ethers::types::BlockId::Number(BlockNumber::Safe);
+        let alloy_block_id = block_id.to_alloy();
+        assert_eq!(alloy_block_id, BlockId::Number(BlockNumberOrTag::Safe));
+        assert_eq!(alloy_block_id.to_ethers(), block_id);
+    }
+
+    #[test]
+    fn test_genesis_block() {
+        let block: Block<TxHash> = serde_json::from_str(GENESIS_BLOCK).unwrap();
+        let alloy_block: alloy::rpc::types::Block = block.clone().try_to_alloy().unwrap();
+
+        assert_eq!(
+            alloy_block.header.hash,
+            Some(B256::from(block.hash.unwrap().0))
+        );
+        assert_eq!(alloy_block.header.number, Some(0));
+        assert_eq!(alloy_block.header.gas_limit, 5000);
+        assert_eq!(alloy_block.header.difficulty, U256::from(0x400000000_u64));
+        assert_eq!(
+            alloy_block.header.nonce.unwrap().as_slice(),
+            &[0, 0, 0, 0, 0, 0, 0, 0x42]
+        );
+        assert!(alloy_block.transactions.is_empty());
+
+        let ethers_block: Block<TxHash> = alloy_block.try_to_ethers().unwrap();
+        assert_eq!(ethers_block, block);
+    }
+
+    #[test]
+    fn test_block_with_transactions() {
+        let block: Block<Transaction> = serde_json::from_str(BLOCK_WITH_TRANSACTIONS).unwrap();
+        let alloy_block: alloy::rpc::types::Block = block.clone().try_to_alloy().unwrap();
+
+        assert_eq!(alloy_block.header.number, Some(18_000_000));
+        assert_eq!(alloy_block.header.base_fee_per_gas, Some(14));
+        assert_eq!(
+            alloy_block.header.hash,
+            Some(
+                "0x09ffeba7b823057dad67d48cec5213bca4b7794b062bcb0b447ad997ad024784"
+                    .parse()
+                    .unwrap()
+            )
+        );
+        assert_eq!(
+            alloy_block.header.miner,
+            "0x90f79bf6eb2c4f870365e785982e1f101e93b906"
+                .parse::<Address>()
+                .unwrap()
+        );
+        let BlockTransactions::Full(ref transactions) = alloy_block.transactions else {
+            panic!("expected full transactions");
+        };
+        assert_eq!(transactions.len(), 3);
+
+        let ethers_block: Block<Transaction> = alloy_block.clone().try_to_ethers().unwrap();
+        assert_eq!(ethers_block, block);
+
+        // a block with full transactions is not a block of transaction hashes
+        let hashes_block: Result<Block<TxHash>, _> = alloy_block.try_to_ethers();
+        assert!(matches!(
+            hashes_block,
+            Err(ConversionError::BlockTransactionsMismatch(_))
+        ));
+    }
+
+    #[test]
+    fn test_transactions() {
+        let block: Block<Transaction> = serde_json::from_str(BLOCK_WITH_TRANSACTIONS).unwrap();
+        let [legacy, eip2930, eip1559]: [Transaction; 3] = block.transactions.try_into().unwrap();
+
+        // the hashes and senders of all transaction types follow from their
+        // encodings and signatures
+        for tx in [&legacy, &eip2930, &eip1559] {
+            assert_eq!(tx.hash(), tx.hash);
+            assert_eq!(tx.recover_from().unwrap(), tx.from);
+        }
+
+        let tx: alloy::rpc::types::Transaction = legacy.clone().try_to_alloy().unwrap();
+        assert_eq!(tx.transaction_type, Some(0));
+        assert_eq!(tx.nonce, 42);
+        assert_eq!(tx.gas, 21000);
+        assert_eq!(tx.gas_price, Some(20_000_000_000));
+        assert_eq!(tx.value, U256::from(1_000_000_000_000_000_000_u128));
+        assert_eq!(tx.chain_id, Some(1));
+        assert_eq!(
+            tx.hash,
+            "0x40e43270e4339bb6645d2e33499bd5020bbc2bac6af4fb816841da0e0074aa29"
+                .parse::<B256>()
+                .unwrap()
+        );
+        let signature = tx.signature.unwrap();
+        assert_eq!(signature.v, U256::from(37));
+        assert_eq!(
+            signature.r,
+            "0x9cdfc5741487b6233d2ec5afa784b205bcc94c556c79d922ac218ffe1e06db1e"
+                .parse::<U256>()
+                .unwrap()
+        );
+        assert_eq!(
+            signature.s,
+            "0x3435ad7451b092997627fe88c9399dfecc8aacf5391a3741dbda7eb9790f7806"
+                .parse::<U256>()
+                .unwrap()
+        );
+        let ethers_tx = TryToEthers::<Transaction>::try_to_ethers(tx).unwrap();
+        assert_eq!(ethers_tx.recover_from().unwrap(), legacy.from);
+        assert_eq!(ethers_tx, legacy);
+
+        let tx: alloy::rpc::types::Transaction = eip2930.clone().try_to_alloy().unwrap();
+        assert_eq!(tx.transaction_type, Some(1));
+        let access_list = tx.access_list.clone().unwrap();
+        assert_eq!(access_list.0[0].address, tx.to.unwrap());
+        assert_eq!(access_list.0[0].storage_keys, vec![B256::with_last_byte(1)]);
+        assert!(!tx.signature.unwrap().y_parity.unwrap().0);
+        let ethers_tx = TryToEthers::<Transaction>::try_to_ethers(tx).unwrap();
+        assert_eq!(ethers_tx.hash(), eip2930.hash);
+        assert_eq!(ethers_tx, eip2930);
+
+        let tx: alloy::rpc::types::Transaction = eip1559.clone().try_to_alloy().unwrap();
+        assert_eq!(tx.transaction_type, Some(2));
+        assert_eq!(tx.to, None);
+        assert_eq!(tx.max_fee_per_gas, Some(2_000_000_000));
+        assert_eq!(tx.max_priority_fee_per_gas, Some(1_000_000_000));
+        assert!(tx.signature.unwrap().y_parity.unwrap().0);
+        let ethers_tx = TryToEthers::<Transaction>::try_to_ethers(tx).unwrap();
+        assert_eq!(ethers_tx.hash(), eip1559.hash);
+        assert_eq!(ethers_tx, eip1559);
+    }
+}
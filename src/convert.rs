@@ -0,0 +1,648 @@
+use alloy::primitives::Uint;
+use std::collections::{BTreeMap, HashMap};
+use std::hash::{BuildHasher, Hash};
+use thiserror::Error;
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum ConversionError {
+    #[error("{from_bits} bits value overflows {to_bits} bits target")]
+    Overflow { from_bits: usize, to_bits: usize },
+    #[error("fixed bytes length {0} is out of the 1 to 32 range")]
+    InvalidFixedBytesLength(usize),
+    #[error("abi value {value} does not match abi type {expected}")]
+    AbiTypeMismatch { expected: String, value: String },
+    #[error("unsupported abi type or value: {0}")]
+    UnsupportedAbi(String),
+    #[error("unsupported transaction type: {0}")]
+    UnsupportedTransactionType(u64),
+    #[error("invalid json field value: {0}")]
+    Json(String),
+    #[error("invalid signature: {0}")]
+    InvalidSignature(String),
+    #[error("unresolved ENS name: {0}")]
+    UnresolvedEnsName(String),
+    #[error("missing required field: {0}")]
+    MissingField(String),
+    #[error("block transactions do not match the target, expected {0}")]
+    BlockTransactionsMismatch(String),
+}
+
+/// Converts an ethers type into its alloy equivalent
+pub trait ToAlloy {
+    /// The alloy type this converts into
+    type To;
+
+    fn to_alloy(self) -> Self::To;
+}
+
+/// Converts an alloy type into its ethers equivalent
+pub trait ToEthers {
+    /// The ethers type this converts into
+    type To;
+
+    fn to_ethers(self) -> Self::To;
+}
+
+/// Fallibly converts an ethers type into the alloy type `T`, used where the
+/// conversion can lose information such as narrowing integer widths
+pub trait TryToAlloy<T> {
+    fn try_to_alloy(self) -> Result<T, ConversionError>;
+}
+
+/// Fallibly converts an alloy type into the ethers type `T`
+pub trait TryToEthers<T> {
+    fn try_to_ethers(self) -> Result<T, ConversionError>;
+}
+
+impl ToAlloy for ethers::types::Address {
+    type To = alloy::primitives::Address;
+
+    fn to_alloy(self) -> Self::To {
+        self.to_fixed_bytes().into()
+    }
+}
+
+impl ToEthers for alloy::primitives::Address {
+    type To = ethers::types::Address;
+
+    fn to_ethers(self) -> Self::To {
+        ethers::types::H160::from(self.into_array())
+    }
+}
+
+impl ToAlloy for ethers::types::U256 {
+    type To = alloy::primitives::U256;
+
+    fn to_alloy(self) -> Self::To {
+        let mut data = [0_u8; 32];
+        self.to_little_endian(&mut data);
+        alloy::primitives::U256::from_le_slice(&data)
+    }
+}
+
+impl ToEthers for alloy::primitives::U256 {
+    type To = ethers::types::U256;
+
+    fn to_ethers(self) -> Self::To {
+        ethers::types::U256::from_little_endian(self.as_le_slice())
+    }
+}
+
+impl ToAlloy for ethers::types::U64 {
+    type To = alloy::primitives::U64;
+
+    fn to_alloy(self) -> Self::To {
+        let mut data = [0_u8; 8];
+        self.to_little_endian(&mut data);
+        alloy::primitives::U64::from_le_slice(&data)
+    }
+}
+
+impl ToEthers for alloy::primitives::U64 {
+    type To = ethers::types::U64;
+
+    fn to_ethers(self) -> Self::To {
+        ethers::types::U64::from_little_endian(self.as_le_slice())
+    }
+}
+
+impl ToAlloy for ethers::types::I256 {
+    type To = alloy::primitives::I256;
+
+    fn to_alloy(self) -> Self::To {
+        alloy::primitives::I256::from_raw(self.into_raw().to_alloy())
+    }
+}
+
+impl ToEthers for alloy::primitives::I256 {
+    type To = ethers::types::I256;
+
+    fn to_ethers(self) -> Self::To {
+        ethers::types::I256::from_raw(self.into_raw().to_ethers())
+    }
+}
+
+impl ToAlloy for ethers::types::Bytes {
+    type To = alloy::primitives::Bytes;
+
+    fn to_alloy(self) -> Self::To {
+        self.to_vec().into()
+    }
+}
+
+impl ToEthers for alloy::primitives::Bytes {
+    type To = ethers::types::Bytes;
+
+    fn to_ethers(self) -> Self::To {
+        self.to_vec().into()
+    }
+}
+
+/// Implements [ToAlloy] and [ToEthers] for an ethers fixed hash type and the
+/// alloy [alloy::primitives::FixedBytes] of the same length
+macro_rules! impl_fixed_hash {
+    ($ethers:ty, $n:literal) => {
+        impl ToAlloy for $ethers {
+            type To = alloy::primitives::FixedBytes<$n>;
+
+            fn to_alloy(self) -> Self::To {
+                self.to_fixed_bytes().into()
+            }
+        }
+
+        impl ToEthers for alloy::primitives::FixedBytes<$n> {
+            type To = $ethers;
+
+            fn to_ethers(self) -> Self::To {
+                <$ethers>::from(self.0)
+            }
+        }
+    };
+}
+
+impl_fixed_hash!(ethers::types::H32, 4);
+impl_fixed_hash!(ethers::types::H64, 8);
+impl_fixed_hash!(ethers::types::H128, 16);
+impl_fixed_hash!(ethers::types::H256, 32);
+impl_fixed_hash!(ethers::types::H512, 64);
+
+impl ToAlloy for ethers::types::transaction::eip2930::AccessList {
+    type To = alloy::rpc::types::AccessList;
+
+    fn to_alloy(self) -> Self::To {
+        alloy::rpc::types::AccessList(
+            self.0
+                .into_iter()
+                .map(|item| alloy::rpc::types::AccessListItem {
+                    address: item.address.to_alloy(),
+                    storage_keys: item.storage_keys.to_alloy(),
+                })
+                .collect(),
+        )
+    }
+}
+
+impl ToEthers for alloy::rpc::types::AccessList {
+    type To = ethers::types::transaction::eip2930::AccessList;
+
+    fn to_ethers(self) -> Self::To {
+        ethers::types::transaction::eip2930::AccessList(
+            self.0
+                .into_iter()
+                .map(|item| ethers::types::transaction::eip2930::AccessListItem {
+                    address: item.address.to_ethers(),
+                    storage_keys: item.storage_keys.to_ethers(),
+                })
+                .collect(),
+        )
+    }
+}
+
+/// Implements [TryToAlloy] from an ethers uint into alloy [Uint] of any width
+/// and [TryToEthers] from alloy [Uint] of any width into the ethers uint,
+/// erroring instead of truncating when the value does not fit the target
+macro_rules! impl_try_uint {
+    ($ethers:ty, $bits:literal) => {
+        impl<const BITS: usize, const LIMBS: usize> TryToAlloy<Uint<BITS, LIMBS>> for $ethers {
+            fn try_to_alloy(self) -> Result<Uint<BITS, LIMBS>, ConversionError> {
+                let mut data = [0_u8; $bits / 8];
+                self.to_little_endian(&mut data);
+                Uint::try_from_le_slice(&data).ok_or(ConversionError::Overflow {
+                    from_bits: $bits,
+                    to_bits: BITS,
+                })
+            }
+        }
+
+        impl<const BITS: usize, const LIMBS: usize> TryToEthers<$ethers> for Uint<BITS, LIMBS> {
+            fn try_to_ethers(self) -> Result<$ethers, ConversionError> {
+                if self.bit_len() > $bits {
+                    return Err(ConversionError::Overflow {
+                        from_bits: BITS,
+                        to_bits: $bits,
+                    });
+                }
+                let data = self.as_le_slice();
+                Ok(<$ethers>::from_little_endian(
+                    &data[..data.len().min($bits / 8)],
+                ))
+            }
+        }
+    };
+}
+
+impl_try_uint!(ethers::types::U64, 64);
+impl_try_uint!(ethers::types::U128, 128);
+impl_try_uint!(ethers::types::U256, 256);
+impl_try_uint!(ethers::types::U512, 512);
+
+/// Implements [TryToAlloy] from an ethers uint into the native `u64`, `u128`
+/// and `usize` integers alloy rpc types use for quantities, erroring instead
+/// of truncating when the value does not fit the target
+macro_rules! impl_try_native {
+    ($ethers:ty, $bits:literal) => {
+        impl TryToAlloy<u64> for $ethers {
+            fn try_to_alloy(self) -> Result<u64, ConversionError> {
+                TryToAlloy::<alloy::primitives::U64>::try_to_alloy(self).map(|value| value.to())
+            }
+        }
+
+        impl TryToAlloy<u128> for $ethers {
+            fn try_to_alloy(self) -> Result<u128, ConversionError> {
+                TryToAlloy::<alloy::primitives::U128>::try_to_alloy(self).map(|value| value.to())
+            }
+        }
+
+        impl TryToAlloy<usize> for $ethers {
+            fn try_to_alloy(self) -> Result<usize, ConversionError> {
+                TryToAlloy::<u64>::try_to_alloy(self)
+                    .ok()
+                    .and_then(|value| usize::try_from(value).ok())
+                    .ok_or(ConversionError::Overflow {
+                        from_bits: $bits,
+                        to_bits: usize::BITS as usize,
+                    })
+            }
+        }
+    };
+}
+
+impl_try_native!(ethers::types::U64, 64);
+impl_try_native!(ethers::types::U128, 128);
+impl_try_native!(ethers::types::U256, 256);
+impl_try_native!(ethers::types::U512, 512);
+
+impl<T: ToAlloy> ToAlloy for Option<T> {
+    type To = Option<T::To>;
+
+    fn to_alloy(self) -> Self::To {
+        self.map(ToAlloy::to_alloy)
+    }
+}
+
+impl<T: ToEthers> ToEthers for Option<T> {
+    type To = Option<T::To>;
+
+    fn to_ethers(self) -> Self::To {
+        self.map(ToEthers::to_ethers)
+    }
+}
+
+impl<T: ToAlloy> ToAlloy for Vec<T> {
+    type To = Vec<T::To>;
+
+    fn to_alloy(self) -> Self::To {
+        self.into_iter().map(ToAlloy::to_alloy).collect()
+    }
+}
+
+impl<T: ToEthers> ToEthers for Vec<T> {
+    type To = Vec<T::To>;
+
+    fn to_ethers(self) -> Self::To {
+        self.into_iter().map(ToEthers::to_ethers).collect()
+    }
+}
+
+impl<T: TryToAlloy<U>, U> TryToAlloy<Option<U>> for Option<T> {
+    fn try_to_alloy(self) -> Result<Option<U>, ConversionError> {
+        self.map(TryToAlloy::try_to_alloy).transpose()
+    }
+}
+
+impl<T: TryToEthers<U>, U> TryToEthers<Option<U>> for Option<T> {
+    fn try_to_ethers(self) -> Result<Option<U>, ConversionError> {
+        self.map(TryToEthers::try_to_ethers).transpose()
+    }
+}
+
+impl<T: TryToAlloy<U>, U> TryToAlloy<Vec<U>> for Vec<T> {
+    fn try_to_alloy(self) -> Result<Vec<U>, ConversionError> {
+        self.into_iter().map(TryToAlloy::try_to_alloy).collect()
+    }
+}
+
+impl<T: TryToEthers<U>, U> TryToEthers<Vec<U>> for Vec<T> {
+    fn try_to_ethers(self) -> Result<Vec<U>, ConversionError> {
+        self.into_iter().map(TryToEthers::try_to_ethers).collect()
+    }
+}
+
+impl<T: ToAlloy, const N: usize> ToAlloy for [T; N] {
+    type To = [T::To; N];
+
+    fn to_alloy(self) -> Self::To {
+        self.map(ToAlloy::to_alloy)
+    }
+}
+
+impl<T: ToEthers, const N: usize> ToEthers for [T; N] {
+    type To = [T::To; N];
+
+    fn to_ethers(self) -> Self::To {
+        self.map(ToEthers::to_ethers)
+    }
+}
+
+/// Only the values of the map are converted, keys are kept as they are
+impl<K: Eq + Hash, V: ToAlloy, S: BuildHasher + Default> ToAlloy for HashMap<K, V, S> {
+    type To = HashMap<K, V::To, S>;
+
+    fn to_alloy(self) -> Self::To {
+        self.into_iter().map(|(k, v)| (k, v.to_alloy())).collect()
+    }
+}
+
+/// Only the values of the map are converted, keys are kept as they are
+impl<K: Eq + Hash, V: ToEthers, S: BuildHasher + Default> ToEthers for HashMap<K, V, S> {
+    type To = HashMap<K, V::To, S>;
+
+    fn to_ethers(self) -> Self::To {
+        self.into_iter().map(|(k, v)| (k, v.to_ethers())).collect()
+    }
+}
+
+/// Only the values of the map are converted, keys are kept as they are
+impl<K: Ord, V: ToAlloy> ToAlloy for BTreeMap<K, V> {
+    type To = BTreeMap<K, V::To>;
+
+    fn to_alloy(self) -> Self::To {
+        self.into_iter().map(|(k, v)| (k, v.to_alloy())).collect()
+    }
+}
+
+/// Only the values of the map are converted, keys are kept as they are
+impl<K: Ord, V: ToEthers> ToEthers for BTreeMap<K, V> {
+    type To = BTreeMap<K, V::To>;
+
+    fn to_ethers(self) -> Self::To {
+        self.into_iter().map(|(k, v)| (k, v.to_ethers())).collect()
+    }
+}
+
+/// Only the values of the map are converted, keys are kept as they are
+impl<K: Ord, V: TryToEthers<U>, U> TryToEthers<BTreeMap<K, U>> for BTreeMap<K, V> {
+    fn try_to_ethers(self) -> Result<BTreeMap<K, U>, ConversionError> {
+        self.into_iter()
+            .map(|(k, v)| Ok((k, v.try_to_ethers()?)))
+            .collect()
+    }
+}
+
+/// Implements [ToAlloy] and [ToEthers] for tuples whose members all implement them
+macro_rules! impl_tuple {
+    ($($name:ident $var:ident),+) => {
+        impl<$($name: ToAlloy),+> ToAlloy for ($($name,)+) {
+            type To = ($(<$name as ToAlloy>::To,)+);
+
+            fn to_alloy(self) -> Self::To {
+                let ($($var,)+) = self;
+                ($($var.to_alloy(),)+)
+            }
+        }
+
+        impl<$($name: ToEthers),+> ToEthers for ($($name,)+) {
+            type To = ($(<$name as ToEthers>::To,)+);
+
+            fn to_ethers(self) -> Self::To {
+                let ($($var,)+) = self;
+                ($($var.to_ethers(),)+)
+            }
+        }
+    };
+}
+
+impl_tuple!(A a);
+impl_tuple!(A a, B b);
+impl_tuple!(A a, B b, C c);
+impl_tuple!(A a, B b, C c, D d);
+impl_tuple!(A a, B b, C c, D d, E e);
+impl_tuple!(A a, B b, C c, D d, E e, F f);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ethers::core::rand::random;
+
+    #[test]
+    fn test_primitives_round_trip() {
+        for _i in 0..10 {
+            let address = ethers::types::Address::random();
+            assert_eq!(address.to_alloy().to_ethers(), address);
+
+            let u256 = ethers::types::U256::from(random::<u128>());
+            assert_eq!(u256.to_alloy().to_ethers(), u256);
+
+            let u64 = ethers::types::U64::from(random::<u64>());
+            assert_eq!(u64.to_alloy().to_ethers(), u64);
+
+            let i256 = ethers::types::I256::from(random::<i128>());
+            assert_eq!(i256.to_alloy().to_ethers(), i256);
+
+            let bytes = ethers::types::Bytes::from(random::<[u8; 32]>().to_vec());
+            assert_eq!(bytes.clone().to_alloy().to_ethers(), bytes);
+
+            let h32 = ethers::types::H32::random();
+            assert_eq!(h32.to_alloy().to_ethers(), h32);
+
+            let h64 = ethers::types::H64::random();
+            assert_eq!(h64.to_alloy().to_ethers(), h64);
+
+            let h128 = ethers::types::H128::random();
+            assert_eq!(h128.to_alloy().to_ethers(), h128);
+
+            let h256 = ethers::types::H256::random();
+            assert_eq!(h256.to_alloy().to_ethers(), h256);
+
+            let h512 = ethers::types::H512::random();
+            assert_eq!(h512.to_alloy().to_ethers(), h512);
+        }
+    }
+
+    #[test]
+    fn test_try_uint_narrowing() {
+        let value = ethers::types::U256::from(u64::MAX);
+        let narrowed: alloy::primitives::U64 = value.try_to_alloy().unwrap();
+        assert_eq!(narrowed, alloy::primitives::U64::from(u64::MAX));
+
+        let value = ethers::types::U256::from(u64::MAX) + 1;
+        let result: Result<alloy::primitives::U64, _> = value.try_to_alloy();
+        assert_eq!(
+            result,
+            Err(ConversionError::Overflow {
+                from_bits: 256,
+                to_bits: 64
+            })
+        );
+
+        let value = alloy::primitives::U256::from(u128::MAX);
+        let narrowed: ethers::types::U128 = value.try_to_ethers().unwrap();
+        assert_eq!(narrowed, ethers::types::U128::from(u128::MAX));
+
+        let value = alloy::primitives::U256::MAX;
+        let result: Result<ethers::types::U128, _> = value.try_to_ethers();
+        assert_eq!(
+            result,
+            Err(ConversionError::Overflow {
+                from_bits: 256,
+                to_bits: 128
+            })
+        );
+
+        let value = ethers::types::U512::from(ethers::types::U256::MAX);
+        let narrowed: alloy::primitives::U256 = value.try_to_alloy().unwrap();
+        assert_eq!(narrowed, alloy::primitives::U256::MAX);
+
+        let value = ethers::types::U512::MAX;
+        let result: Result<alloy::primitives::U256, _> = value.try_to_alloy();
+        assert_eq!(
+            result,
+            Err(ConversionError::Overflow {
+                from_bits: 512,
+                to_bits: 256
+            })
+        );
+    }
+
+    #[test]
+    fn test_try_uint_native() {
+        // the value of an ethers uint as a native integer, None if the value
+        // does not fit the ethers uint in the first place
+        macro_rules! try_native {
+            ($ethers:ty, $native:ty, $value:expr) => {
+                <$ethers>::from_dec_str($value).ok().map(|value| {
+                    TryToAlloy::<$native>::try_to_alloy(value).map(|value| value.to_string())
+                })
+            };
+        }
+
+        let values = [
+            "42",
+            "18446744073709551615",
+            "18446744073709551616",
+            "340282366920938463463374607431768211455",
+            "340282366920938463463374607431768211456",
+        ];
+        for value in values {
+            let bits = ethers::types::U512::from_dec_str(value).unwrap().bits();
+            let expected = |from_bits: usize, to_bits: usize| {
+                if bits > from_bits {
+                    None
+                } else if bits > to_bits {
+                    Some(Err(ConversionError::Overflow { from_bits, to_bits }))
+                } else {
+                    Some(Ok(value.to_string()))
+                }
+            };
+
+            macro_rules! check {
+                ($($ethers:ty => $from_bits:expr),*) => {$(
+                    assert_eq!(try_native!($ethers, u64, value), expected($from_bits, 64));
+                    assert_eq!(try_native!($ethers, u128, value), expected($from_bits, 128));
+                    assert_eq!(
+                        try_native!($ethers, usize, value),
+                        expected($from_bits, usize::BITS as usize)
+                    );
+                )*};
+            }
+            check!(
+                ethers::types::U64 => 64,
+                ethers::types::U128 => 128,
+                ethers::types::U256 => 256,
+                ethers::types::U512 => 512
+            );
+        }
+    }
+
+    #[test]
+    fn test_try_uint_widening() {
+        for _i in 0..10 {
+            let value = random::<u64>();
+            let widened: alloy::primitives::U512 =
+                ethers::types::U64::from(value).try_to_alloy().unwrap();
+            assert_eq!(widened, alloy::primitives::U512::from(value));
+
+            let widened: ethers::types::U512 =
+                alloy::primitives::U64::from(value).try_to_ethers().unwrap();
+            assert_eq!(widened, ethers::types::U512::from(value));
+        }
+
+        let widened: alloy::primitives::U512 = ethers::types::U256::MAX.try_to_alloy().unwrap();
+        assert_eq!(
+            widened,
+            alloy::primitives::U512::from(alloy::primitives::U256::MAX)
+        );
+    }
+
+    #[test]
+    fn test_option() {
+        let address = alloy::primitives::Address::random();
+        assert_eq!(
+            Some(address).to_ethers(),
+            Some(ethers::types::H160(address.into_array()))
+        );
+        assert_eq!(None::<alloy::primitives::Address>.to_ethers(), None);
+    }
+
+    #[test]
+    fn test_vec() {
+        let hashes: Vec<ethers::types::H256> =
+            (0..10).map(|_| ethers::types::H256::random()).collect();
+        let alloy_hashes = hashes.clone().to_alloy();
+        assert_eq!(alloy_hashes.len(), hashes.len());
+        for (alloy_hash, hash) in alloy_hashes.iter().zip(hashes.iter()) {
+            assert_eq!(alloy_hash.as_slice(), hash.as_bytes());
+        }
+        assert_eq!(alloy_hashes.to_ethers(), hashes);
+    }
+
+    #[test]
+    fn test_array() {
+        let values = [
+            alloy::primitives::U256::from(1),
+            alloy::primitives::U256::from(2),
+            alloy::primitives::U256::MAX,
+        ];
+        let expected = [
+            ethers::types::U256::from(1),
+            ethers::types::U256::from(2),
+            ethers::types::U256::MAX,
+        ];
+        assert_eq!(values.to_ethers(), expected);
+        assert_eq!(expected.to_alloy(), values);
+    }
+
+    #[test]
+    fn test_tuple() {
+        let address = ethers::types::Address::random();
+        let value = ethers::types::U256::from(random::<u64>());
+        let hash = Some(ethers::types::H256::random());
+
+        let (alloy_address, alloy_value, alloy_hash) = (address, value, hash).to_alloy();
+        assert_eq!(alloy_address.as_slice(), address.as_bytes());
+        assert_eq!(alloy_value, alloy::primitives::U256::from(value.as_u64()));
+        assert_eq!(alloy_hash.unwrap().as_slice(), hash.unwrap().as_bytes());
+
+        assert_eq!(
+            (alloy_address, alloy_value, alloy_hash).to_ethers(),
+            (address, value, hash)
+        );
+    }
+
+    #[test]
+    fn test_hash_map() {
+        let mut balances = HashMap::new();
+        for i in 0..10_u64 {
+            balances.insert(format!("account-{}", i), ethers::types::U256::from(i));
+        }
+
+        let alloy_balances = balances.clone().to_alloy();
+        for i in 0..10_u64 {
+            assert_eq!(
+                alloy_balances[&format!("account-{}", i)],
+                alloy::primitives::U256::from(i)
+            );
+        }
+        assert_eq!(alloy_balances.to_ethers(), balances);
+    }
+}
@@ -1,63 +1,149 @@
+pub mod abi;
+pub mod authorization;
+#[cfg(feature = "eip4844")]
+pub mod blob;
+pub mod block;
 #[cfg(not(target_family = "wasm"))]
 pub mod client;
+pub mod convert;
 pub mod gas_fee_middleware;
 pub mod multicall;
+pub mod overrides;
+pub mod receipt;
 pub mod request_shim;
 pub mod rpc;
+pub mod serde_compat;
+pub mod signature;
 pub mod transaction;
 pub mod utils;
 
+pub use convert::{ConversionError, ToAlloy, ToEthers, TryToAlloy, TryToEthers};
+
 /// Converts [ethers::types::Address] to [alloy::primitives::Address]
 pub fn ethers_address_to_alloy(address: ethers::types::Address) -> alloy::primitives::Address {
-    address.to_fixed_bytes().into()
+    address.to_alloy()
 }
 
 /// Converts [alloy::primitives::Address] to [ethers::types::Address]
 pub fn alloy_address_to_ethers(address: alloy::primitives::Address) -> ethers::types::Address {
-    ethers::types::H160::from(address.into_array())
+    address.to_ethers()
 }
 
 /// Converts [ethers::types::U256] to [alloy::primitives::U256]
 pub fn ethers_u256_to_alloy(value: ethers::types::U256) -> alloy::primitives::U256 {
-    let mut data = [0_u8; 32];
-    value.to_little_endian(&mut data);
-    alloy::primitives::U256::from_le_slice(&data)
+    value.to_alloy()
 }
 
 /// Converts [alloy::primitives::U256] to [ethers::types::U256]
 pub fn alloy_u256_to_ethers(value: alloy::primitives::U256) -> ethers::types::U256 {
-    let data = value.as_le_slice();
-    ethers::types::U256::from_little_endian(data)
+    value.to_ethers()
 }
 
 /// Converts [ethers::types::U64] to [alloy::primitives::U64]
 pub fn ethers_u64_to_alloy(value: ethers::types::U64) -> alloy::primitives::U64 {
-    let mut data = [0_u8; 8];
-    value.to_little_endian(&mut data);
-    alloy::primitives::U64::from_le_slice(&data)
+    value.to_alloy()
 }
 
 /// Converts [alloy::primitives::U64] to [ethers::types::U64]
 pub fn alloy_u64_to_ethers(value: alloy::primitives::U64) -> ethers::types::U64 {
-    let data = value.as_le_slice();
-    ethers::types::U64::from_little_endian(data)
+    value.to_ethers()
+}
+
+/// Converts [ethers::types::I256] to [alloy::primitives::I256], both are
+/// stored as two's complement so the raw 256 bits are carried over as is
+pub fn ethers_i256_to_alloy(value: ethers::types::I256) -> alloy::primitives::I256 {
+    value.to_alloy()
+}
+
+/// Converts [alloy::primitives::I256] to [ethers::types::I256]
+pub fn alloy_i256_to_ethers(value: alloy::primitives::I256) -> ethers::types::I256 {
+    value.to_ethers()
 }
 
 /// Converts [ethers::types::Bytes] to [alloy::primitives::Bytes]
 pub fn ethers_bytes_to_alloy(bytes: ethers::types::Bytes) -> alloy::primitives::Bytes {
-    bytes.to_vec().into()
+    bytes.to_alloy()
 }
 
 /// Converts [alloy::primitives::Bytes]to [ethers::types::Bytes]
 pub fn alloy_bytes_to_ethers(bytes: alloy::primitives::Bytes) -> ethers::types::Bytes {
-    bytes.to_vec().into()
+    bytes.to_ethers()
+}
+
+/// Converts [ethers::types::H256] to [alloy::primitives::B256]
+pub fn ethers_h256_to_alloy(hash: ethers::types::H256) -> alloy::primitives::B256 {
+    hash.to_alloy()
+}
+
+/// Converts [alloy::primitives::B256] to [ethers::types::H256]
+pub fn alloy_b256_to_ethers(hash: alloy::primitives::B256) -> ethers::types::H256 {
+    hash.to_ethers()
+}
+
+/// Converts [ethers::types::H512] to [alloy::primitives::B512]
+pub fn ethers_h512_to_alloy(hash: ethers::types::H512) -> alloy::primitives::B512 {
+    hash.to_alloy()
+}
+
+/// Converts [alloy::primitives::B512] to [ethers::types::H512]
+pub fn alloy_b512_to_ethers(hash: alloy::primitives::B512) -> ethers::types::H512 {
+    hash.to_ethers()
+}
+
+/// Converts [ethers::types::H128] to [alloy::primitives::B128]
+pub fn ethers_h128_to_alloy(hash: ethers::types::H128) -> alloy::primitives::B128 {
+    hash.to_alloy()
+}
+
+/// Converts [alloy::primitives::B128] to [ethers::types::H128]
+pub fn alloy_b128_to_ethers(hash: alloy::primitives::B128) -> ethers::types::H128 {
+    hash.to_ethers()
+}
+
+/// Converts [ethers::types::H64] to [alloy::primitives::B64]
+pub fn ethers_h64_to_alloy(hash: ethers::types::H64) -> alloy::primitives::B64 {
+    hash.to_alloy()
+}
+
+/// Converts [alloy::primitives::B64] to [ethers::types::H64]
+pub fn alloy_b64_to_ethers(hash: alloy::primitives::B64) -> ethers::types::H64 {
+    hash.to_ethers()
+}
+
+/// Converts [ethers::types::H32] to [alloy::primitives::FixedBytes<4>]
+pub fn ethers_h32_to_alloy(hash: ethers::types::H32) -> alloy::primitives::FixedBytes<4> {
+    hash.to_alloy()
+}
+
+/// Converts [alloy::primitives::FixedBytes<4>] to [ethers::types::H32]
+pub fn alloy_fixed_bytes_4_to_ethers(hash: alloy::primitives::FixedBytes<4>) -> ethers::types::H32 {
+    hash.to_ethers()
+}
+
+/// Converts a raw ethers fixed size byte array (as found in `bytesN` abi
+/// values) to [alloy::primitives::FixedBytes]
+pub fn ethers_fixed_bytes_to_alloy<const N: usize>(
+    bytes: [u8; N],
+) -> alloy::primitives::FixedBytes<N> {
+    alloy::primitives::FixedBytes::new(bytes)
+}
+
+/// Converts [alloy::primitives::FixedBytes] to a raw fixed size byte array
+pub fn alloy_fixed_bytes_to_ethers<const N: usize>(
+    bytes: alloy::primitives::FixedBytes<N>,
+) -> [u8; N] {
+    bytes.0
 }
 
 #[cfg(test)]
 pub mod test {
     use crate::{
-        alloy_address_to_ethers, alloy_bytes_to_ethers, alloy_u256_to_ethers,
-        ethers_address_to_alloy, ethers_bytes_to_alloy, ethers_u256_to_alloy,
+        alloy_address_to_ethers, alloy_b128_to_ethers, alloy_b256_to_ethers, alloy_b512_to_ethers,
+        alloy_b64_to_ethers, alloy_bytes_to_ethers, alloy_fixed_bytes_4_to_ethers,
+        alloy_fixed_bytes_to_ethers, alloy_i256_to_ethers, alloy_u256_to_ethers,
+        ethers_address_to_alloy, ethers_bytes_to_alloy, ethers_fixed_bytes_to_alloy,
+        ethers_h128_to_alloy, ethers_h256_to_alloy, ethers_h32_to_alloy, ethers_h512_to_alloy,
+        ethers_h64_to_alloy, ethers_i256_to_alloy, ethers_u256_to_alloy,
     };
     use ethers::core::rand::random;
 
@@ -97,6 +183,57 @@ pub mod test {
         }
     }
 
+    #[test]
+    pub fn test_ethers_i256_to_alloy() {
+        let cases = [
+            (ethers::types::I256::MIN, alloy::primitives::I256::MIN),
+            (ethers::types::I256::MAX, alloy::primitives::I256::MAX),
+            (
+                ethers::types::I256::minus_one(),
+                alloy::primitives::I256::MINUS_ONE,
+            ),
+            (ethers::types::I256::zero(), alloy::primitives::I256::ZERO),
+        ];
+        for (ethers_i256, expected) in cases {
+            let alloy_i256 = ethers_i256_to_alloy(ethers_i256);
+            assert_eq!(alloy_i256, expected);
+            assert_eq!(alloy_i256.to_string(), ethers_i256.to_string());
+            assert!(ethers_i256.eq(&alloy_i256_to_ethers(alloy_i256)));
+        }
+
+        for _i in 0..10 {
+            let ethers_i256 = ethers::types::I256::from(random::<i128>());
+            let alloy_i256 = ethers_i256_to_alloy(ethers_i256);
+            assert_eq!(alloy_i256.to_string(), ethers_i256.to_string());
+            assert!(ethers_i256.eq(&alloy_i256_to_ethers(alloy_i256)));
+        }
+    }
+
+    #[test]
+    pub fn test_alloy_i256_to_ethers() {
+        let cases = [
+            (alloy::primitives::I256::MIN, ethers::types::I256::MIN),
+            (alloy::primitives::I256::MAX, ethers::types::I256::MAX),
+            (
+                alloy::primitives::I256::MINUS_ONE,
+                ethers::types::I256::minus_one(),
+            ),
+            (alloy::primitives::I256::ZERO, ethers::types::I256::zero()),
+        ];
+        for (alloy_i256, expected) in cases {
+            let ethers_i256 = alloy_i256_to_ethers(alloy_i256);
+            assert_eq!(ethers_i256, expected);
+            assert!(alloy_i256.eq(&ethers_i256_to_alloy(ethers_i256)));
+        }
+
+        for _i in 0..10 {
+            let alloy_i256 = alloy::primitives::I256::try_from(random::<i128>()).unwrap();
+            let ethers_i256 = alloy_i256_to_ethers(alloy_i256);
+            assert_eq!(ethers_i256.to_string(), alloy_i256.to_string());
+            assert!(alloy_i256.eq(&ethers_i256_to_alloy(ethers_i256)));
+        }
+    }
+
     #[test]
     pub fn test_ethers_bytes_to_alloy() {
         for _i in 0..10 {
@@ -116,4 +253,118 @@ pub mod test {
             assert!(alloy_bytes.eq(&ethers_bytes_to_alloy(ethers_bytes)));
         }
     }
+
+    #[test]
+    pub fn test_ethers_h256_to_alloy() {
+        for _i in 0..10 {
+            let ethers_h256 = ethers::types::H256::random();
+            let alloy_b256 = ethers_h256_to_alloy(ethers_h256);
+            assert_eq!(alloy_b256.as_slice(), ethers_h256.as_bytes());
+            assert!(ethers_h256.eq(&alloy_b256_to_ethers(alloy_b256)));
+        }
+    }
+
+    #[test]
+    pub fn test_alloy_b256_to_ethers() {
+        for _i in 0..10 {
+            let alloy_b256 = alloy::primitives::B256::random();
+            let ethers_h256 = alloy_b256_to_ethers(alloy_b256);
+            assert!(alloy_b256.eq(&ethers_h256_to_alloy(ethers_h256)));
+        }
+    }
+
+    #[test]
+    pub fn test_ethers_h512_to_alloy() {
+        for _i in 0..10 {
+            let ethers_h512 = ethers::types::H512::random();
+            let alloy_b512 = ethers_h512_to_alloy(ethers_h512);
+            assert_eq!(alloy_b512.as_slice(), ethers_h512.as_bytes());
+            assert!(ethers_h512.eq(&alloy_b512_to_ethers(alloy_b512)));
+        }
+    }
+
+    #[test]
+    pub fn test_alloy_b512_to_ethers() {
+        for _i in 0..10 {
+            let alloy_b512 = alloy::primitives::B512::random();
+            let ethers_h512 = alloy_b512_to_ethers(alloy_b512);
+            assert!(alloy_b512.eq(&ethers_h512_to_alloy(ethers_h512)));
+        }
+    }
+
+    #[test]
+    pub fn test_ethers_h128_to_alloy() {
+        for _i in 0..10 {
+            let ethers_h128 = ethers::types::H128::random();
+            let alloy_b128 = ethers_h128_to_alloy(ethers_h128);
+            assert_eq!(alloy_b128.as_slice(), ethers_h128.as_bytes());
+            assert!(ethers_h128.eq(&alloy_b128_to_ethers(alloy_b128)));
+        }
+    }
+
+    #[test]
+    pub fn test_alloy_b128_to_ethers() {
+        for _i in 0..10 {
+            let alloy_b128 = alloy::primitives::B128::random();
+            let ethers_h128 = alloy_b128_to_ethers(alloy_b128);
+            assert!(alloy_b128.eq(&ethers_h128_to_alloy(ethers_h128)));
+        }
+    }
+
+    #[test]
+    pub fn test_ethers_h64_to_alloy() {
+        for _i in 0..10 {
+            let ethers_h64 = ethers::types::H64::random();
+            let alloy_b64 = ethers_h64_to_alloy(ethers_h64);
+            assert_eq!(alloy_b64.as_slice(), ethers_h64.as_bytes());
+            assert!(ethers_h64.eq(&alloy_b64_to_ethers(alloy_b64)));
+        }
+    }
+
+    #[test]
+    pub fn test_alloy_b64_to_ethers() {
+        for _i in 0..10 {
+            let alloy_b64 = alloy::primitives::B64::random();
+            let ethers_h64 = alloy_b64_to_ethers(alloy_b64);
+            assert!(alloy_b64.eq(&ethers_h64_to_alloy(ethers_h64)));
+        }
+    }
+
+    #[test]
+    pub fn test_ethers_h32_to_alloy() {
+        for _i in 0..10 {
+            let ethers_h32 = ethers::types::H32::random();
+            let alloy_fixed_bytes = ethers_h32_to_alloy(ethers_h32);
+            assert_eq!(alloy_fixed_bytes.as_slice(), ethers_h32.as_bytes());
+            assert!(ethers_h32.eq(&alloy_fixed_bytes_4_to_ethers(alloy_fixed_bytes)));
+        }
+    }
+
+    #[test]
+    pub fn test_alloy_fixed_bytes_4_to_ethers() {
+        for _i in 0..10 {
+            let alloy_fixed_bytes = alloy::primitives::FixedBytes::<4>::random();
+            let ethers_h32 = alloy_fixed_bytes_4_to_ethers(alloy_fixed_bytes);
+            assert!(alloy_fixed_bytes.eq(&ethers_h32_to_alloy(ethers_h32)));
+        }
+    }
+
+    #[test]
+    pub fn test_ethers_fixed_bytes_to_alloy() {
+        for _i in 0..10 {
+            let ethers_bytes: [u8; 20] = random();
+            let alloy_fixed_bytes = ethers_fixed_bytes_to_alloy(ethers_bytes);
+            assert_eq!(alloy_fixed_bytes.as_slice(), &ethers_bytes);
+            assert!(ethers_bytes.eq(&alloy_fixed_bytes_to_ethers(alloy_fixed_bytes)));
+        }
+    }
+
+    #[test]
+    pub fn test_alloy_fixed_bytes_to_ethers() {
+        for _i in 0..10 {
+            let alloy_fixed_bytes = alloy::primitives::FixedBytes::<7>::random();
+            let ethers_bytes = alloy_fixed_bytes_to_ethers(alloy_fixed_bytes);
+            assert!(alloy_fixed_bytes.eq(&ethers_fixed_bytes_to_alloy(ethers_bytes)));
+        }
+    }
 }
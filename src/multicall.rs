@@ -1,8 +1,11 @@
+use crate::overrides::{BlockOverrides, StateOverride};
 use crate::transaction::{ReadContractParameters, ReadableClient, ReadableClientError};
 use alloy::primitives::U256;
-use alloy::primitives::{hex::FromHex, Address, U64};
+use alloy::primitives::{hex::FromHex, Address};
+use alloy::rpc::types::BlockId;
 use alloy::sol;
 use alloy::sol_types::SolCall;
+use derive_builder::Builder;
 use ethers::providers::JsonRpcClient;
 use thiserror::Error;
 
@@ -44,6 +47,25 @@ pub struct Multicall<T: SolCall> {
     pub calls: Vec<MulticallCallItem<T>>,
 }
 
+/// Optional parameters of [Multicall::read]
+#[derive(Builder, Clone, Debug, Default)]
+pub struct MulticallReadParameters {
+    /// Block to read at, latest if not set
+    #[builder(setter(into), default)]
+    pub block: Option<BlockId>,
+    #[builder(setter(into), default)]
+    pub gas: Option<U256>,
+    /// Multicall3 address to call instead of [MULTICALL3_ADDRESS]
+    #[builder(setter(into), default)]
+    pub multicall_address_override: Option<Address>,
+    /// Account state overrides to simulate the calls against
+    #[builder(setter(into), default)]
+    pub state_override: Option<StateOverride>,
+    /// Block environment overrides to simulate the calls in
+    #[builder(setter(into), default)]
+    pub block_overrides: Option<BlockOverrides>,
+}
+
 impl<T: SolCall> Default for Multicall<T> {
     fn default() -> Self {
         Multicall { calls: vec![] }
@@ -66,13 +88,13 @@ impl<T: SolCall> Multicall<T> {
     /// Executes the read call through the given JsonRpcClient provider with the
     /// calls already added to the list, the Multicall3 address on all chains is
     /// the same, except a few that have unofficial deployments such as zkSynEra,
-    /// in such cases the default Multicall3 address can be overriden in the args
+    /// in such cases the default Multicall3 address can be overriden in the
+    /// parameters, which also set the block to read at and the account state
+    /// and block environment overrides to simulate the calls with
     pub async fn read(
         &self,
         provider: &ReadableClient<impl JsonRpcClient>,
-        block_number: Option<u64>,
-        gas: Option<U256>,
-        multicall_address_override: Option<Address>,
+        parameters: MulticallReadParameters,
     ) -> Result<Vec<Result<Result<T::Return, MulticallError>, MulticallError>>, MulticallError>
     {
         let calls = self
@@ -86,11 +108,15 @@ impl<T: SolCall> Multicall<T> {
             .collect::<Vec<self::IMulticall3::Call3>>();
 
         let params = ReadContractParameters {
-            address: multicall_address_override
+            address: parameters
+                .multicall_address_override
                 .unwrap_or(Address::from_hex(MULTICALL3_ADDRESS).unwrap()),
             call: self::IMulticall3::aggregate3Call { calls },
-            block_number: block_number.map(U64::from),
-            gas,
+            block: parameters.block,
+            gas: parameters.gas,
+            transaction_kind: None,
+            state_override: parameters.state_override,
+            block_overrides: parameters.block_overrides,
         };
 
         let result = provider.read(params).await?;
@@ -112,6 +138,7 @@ impl<T: SolCall> Multicall<T> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::overrides::AccountOverride;
     use crate::{multicall::IMulticall3::Result as MulticallResult, rpc::Response};
     use alloy::{hex::encode_prefixed, primitives::B256, sol_types::SolValue};
     use httpmock::{Method::POST, MockServer};
@@ -190,7 +217,9 @@ mod tests {
         });
 
         let provider = ReadableClient::new_from_url(rpc_server.url("/rpc"))?;
-        let result = multicall.read(&provider, None, None, None).await?;
+        let result = multicall
+            .read(&provider, MulticallReadParameters::default())
+            .await?;
         let mut result_symbols = vec![];
         for res in result {
             result_symbols.push(res??._0);
@@ -201,4 +230,59 @@ mod tests {
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn test_multicall_read_with_state_override() -> anyhow::Result<()> {
+        let rpc_server = MockServer::start();
+        let mut multicall = Multicall::default();
+
+        let token = Address::repeat_byte(0x11);
+        multicall.add_call(MulticallCallItem {
+            address: token,
+            call: symbolCall {},
+        });
+
+        let response_data = vec![MulticallResult {
+            success: true,
+            returnData: "TKN".abi_encode().into(),
+        }]
+        .abi_encode();
+
+        // only answers if the override is sent along the multicall call data
+        rpc_server.mock(|when, then| {
+            when.method(POST)
+                .path("/rpc")
+                .body_contains("0x82ad56cb")
+                .body_contains(r#""0x1111111111111111111111111111111111111111":{"code":"0x6000"}"#);
+            then.json_body_obj(
+                &from_str::<Value>(
+                    &Response::new_success(1, encode_prefixed(response_data).as_str())
+                        .to_json_string()
+                        .unwrap(),
+                )
+                .unwrap(),
+            );
+        });
+
+        let state_override = StateOverride::from([(
+            token,
+            AccountOverride {
+                code: Some(vec![0x60, 0x00].into()),
+                ..Default::default()
+            },
+        )]);
+        let provider = ReadableClient::new_from_url(rpc_server.url("/rpc"))?;
+        let parameters = MulticallReadParametersBuilder::default()
+            .state_override(state_override)
+            .build()?;
+        let result = multicall.read(&provider, parameters).await?;
+
+        let symbols = result
+            .into_iter()
+            .map(|res| Ok(res??._0))
+            .collect::<Result<Vec<_>, MulticallError>>()?;
+        assert_eq!(symbols, vec!["TKN".to_string()]);
+
+        Ok(())
+    }
 }
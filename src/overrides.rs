@@ -0,0 +1,5 @@
+// eth_call account state and block environment overrides, taken from alloy so
+// they match its rpc types, the state override is passed as the third param
+// and the block overrides as the fourth, which older nodes reject
+pub use alloy::rpc::types::state::{AccountOverride, StateOverride};
+pub use alloy::rpc::types::BlockOverrides;
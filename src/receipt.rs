@@ -0,0 +1,362 @@
+use crate::convert::{ConversionError, ToAlloy, ToEthers, TryToAlloy};
+use alloy::consensus::{Eip658Value, Receipt, ReceiptEnvelope, ReceiptWithBloom};
+
+impl ToAlloy for ethers::types::Bloom {
+    type To = alloy::primitives::Bloom;
+
+    fn to_alloy(self) -> Self::To {
+        alloy::primitives::Bloom::from(self.to_fixed_bytes())
+    }
+}
+
+impl ToEthers for alloy::primitives::Bloom {
+    type To = ethers::types::Bloom;
+
+    fn to_ethers(self) -> Self::To {
+        ethers::types::Bloom::from(self.0 .0)
+    }
+}
+
+/// Converts the log address, topics and data only, which is what alloy
+/// [SolEvent](alloy::sol_types::SolEvent) decoding works with, use
+/// [TryToAlloy] into [alloy::rpc::types::Log] to keep the block and
+/// transaction metadata
+impl ToAlloy for ethers::types::Log {
+    type To = alloy::primitives::Log;
+
+    fn to_alloy(self) -> Self::To {
+        alloy::primitives::Log::new_unchecked(
+            self.address.to_alloy(),
+            self.topics.to_alloy(),
+            self.data.to_alloy(),
+        )
+    }
+}
+
+impl ToEthers for alloy::primitives::Log {
+    type To = ethers::types::Log;
+
+    fn to_ethers(self) -> Self::To {
+        ethers::types::Log {
+            address: self.address.to_ethers(),
+            topics: self.data.topics().to_vec().to_ethers(),
+            data: self.data.data.to_ethers(),
+            ..Default::default()
+        }
+    }
+}
+
+impl TryToAlloy<alloy::rpc::types::Log> for ethers::types::Log {
+    fn try_to_alloy(self) -> Result<alloy::rpc::types::Log, ConversionError> {
+        Ok(alloy::rpc::types::Log {
+            block_hash: self.block_hash.to_alloy(),
+            block_number: self.block_number.map(|number| number.as_u64()),
+            block_timestamp: None,
+            transaction_hash: self.transaction_hash.to_alloy(),
+            transaction_index: self.transaction_index.map(|index| index.as_u64()),
+            log_index: self.log_index.try_to_alloy()?,
+            removed: self.removed.unwrap_or(false),
+            inner: self.to_alloy(),
+        })
+    }
+}
+
+impl ToEthers for alloy::rpc::types::Log {
+    type To = ethers::types::Log;
+
+    fn to_ethers(self) -> Self::To {
+        ethers::types::Log {
+            block_hash: self.block_hash.to_ethers(),
+            block_number: self.block_number.map(Into::into),
+            transaction_hash: self.transaction_hash.to_ethers(),
+            transaction_index: self.transaction_index.map(Into::into),
+            log_index: self.log_index.map(Into::into),
+            removed: Some(self.removed),
+            ..self.inner.to_ethers()
+        }
+    }
+}
+
+/// Receipts without a transaction type are taken as legacy receipts, pre
+/// Byzantium receipts without a status keep their post transaction state root
+impl TryToAlloy<alloy::rpc::types::TransactionReceipt> for ethers::types::TransactionReceipt {
+    fn try_to_alloy(self) -> Result<alloy::rpc::types::TransactionReceipt, ConversionError> {
+        let receipt = ReceiptWithBloom {
+            receipt: Receipt {
+                status: match (self.status, self.root) {
+                    (None, Some(root)) => Eip658Value::PostState(root.to_alloy()),
+                    (status, _) => (status == Some(ethers::types::U64::one())).into(),
+                },
+                cumulative_gas_used: self.cumulative_gas_used.try_to_alloy()?,
+                logs: self.logs.try_to_alloy()?,
+            },
+            logs_bloom: self.logs_bloom.to_alloy(),
+        };
+        let inner = match self.transaction_type.map(|ty| ty.as_u64()) {
+            None | Some(0) => ReceiptEnvelope::Legacy(receipt),
+            Some(1) => ReceiptEnvelope::Eip2930(receipt),
+            Some(2) => ReceiptEnvelope::Eip1559(receipt),
+            Some(3) => ReceiptEnvelope::Eip4844(receipt),
+            Some(ty) => return Err(ConversionError::UnsupportedTransactionType(ty)),
+        };
+
+        Ok(alloy::rpc::types::TransactionReceipt {
+            inner,
+            transaction_hash: self.transaction_hash.to_alloy(),
+            transaction_index: Some(self.transaction_index.as_u64()),
+            block_hash: self.block_hash.to_alloy(),
+            block_number: self.block_number.map(|number| number.as_u64()),
+            gas_used: self
+                .gas_used
+                .ok_or(ConversionError::MissingField("gas_used".to_string()))?
+                .try_to_alloy()?,
+            effective_gas_price: self
+                .effective_gas_price
+                .ok_or(ConversionError::MissingField(
+                    "effective_gas_price".to_string(),
+                ))?
+                .try_to_alloy()?,
+            blob_gas_used: None,
+            blob_gas_price: None,
+            from: self.from.to_alloy(),
+            to: self.to.to_alloy(),
+            contract_address: self.contract_address.to_alloy(),
+            state_root: self.root.to_alloy(),
+            authorization_list: None,
+        })
+    }
+}
+
+impl ToEthers for alloy::rpc::types::TransactionReceipt {
+    type To = ethers::types::TransactionReceipt;
+
+    fn to_ethers(self) -> Self::To {
+        ethers::types::TransactionReceipt {
+            transaction_hash: self.transaction_hash.to_ethers(),
+            transaction_index: self.transaction_index.unwrap_or_default().into(),
+            block_hash: self.block_hash.to_ethers(),
+            block_number: self.block_number.map(Into::into),
+            from: self.from.to_ethers(),
+            to: self.to.to_ethers(),
+            cumulative_gas_used: self.inner.cumulative_gas_used().into(),
+            gas_used: Some(self.gas_used.into()),
+            contract_address: self.contract_address.to_ethers(),
+            logs: self.inner.logs().to_vec().to_ethers(),
+            status: match self.inner.as_receipt().map(|receipt| &receipt.status) {
+                Some(Eip658Value::PostState(_)) => None,
+                _ => Some(u64::from(self.inner.is_success()).into()),
+            },
+            root: self.state_root.to_ethers(),
+            logs_bloom: self.inner.logs_bloom().to_ethers(),
+            transaction_type: Some(u64::from(u8::from(self.inner.tx_type())).into()),
+            effective_gas_price: Some(self.effective_gas_price.into()),
+            ..Default::default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::primitives::{Address, U256};
+    use alloy::sol;
+    use alloy::sol_types::SolEvent;
+    use ethers::types::{Bytes, H160, H256, U64};
+
+    sol! {
+        event Transfer(address indexed from, address indexed to, uint256 value);
+    }
+
+    fn transfer_log() -> ethers::types::Log {
+        ethers::types::Log {
+            address: H160::repeat_byte(0x11),
+            topics: vec![
+                H256(Transfer::SIGNATURE_HASH.0),
+                H256::from(H160::repeat_byte(0x22)),
+                H256::from(H160::repeat_byte(0x33)),
+            ],
+            data: Bytes::from(U256::from(1000).to_be_bytes::<32>().to_vec()),
+            block_hash: Some(H256::repeat_byte(0x44)),
+            block_number: Some(U64::from(100)),
+            transaction_hash: Some(H256::repeat_byte(0x55)),
+            transaction_index: Some(U64::from(2)),
+            log_index: Some(ethers::types::U256::from(7)),
+            removed: Some(false),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_bloom_round_trip() {
+        let bloom = ethers::types::Bloom::from_low_u64_be(0x1234);
+        let alloy_bloom = bloom.to_alloy();
+        assert_eq!(alloy_bloom.as_slice(), bloom.as_bytes());
+        assert_eq!(alloy_bloom.to_ethers(), bloom);
+    }
+
+    #[test]
+    fn test_log_decode() {
+        let log = transfer_log().to_alloy();
+        let transfer =
+            Transfer::decode_raw_log(log.data.topics().iter().copied(), &log.data.data, true)
+                .unwrap();
+        assert_eq!(transfer.from, Address::repeat_byte(0x22));
+        assert_eq!(transfer.to, Address::repeat_byte(0x33));
+        assert_eq!(transfer.value, U256::from(1000));
+
+        let ethers_log = log.to_ethers();
+        assert_eq!(ethers_log.topics, transfer_log().topics);
+        assert_eq!(ethers_log.data, transfer_log().data);
+        assert_eq!(ethers_log.block_hash, None);
+    }
+
+    #[test]
+    fn test_rpc_log_round_trip() {
+        let log: alloy::rpc::types::Log = transfer_log().try_to_alloy().unwrap();
+        assert_eq!(log.inner.address, Address::repeat_byte(0x11));
+        assert_eq!(log.block_number, Some(100));
+        assert_eq!(log.transaction_index, Some(2));
+        assert_eq!(log.log_index, Some(7));
+        assert!(!log.removed);
+
+        assert_eq!(log.to_ethers(), transfer_log());
+    }
+
+    #[test]
+    fn test_rpc_log_index_overflow() {
+        let log = ethers::types::Log {
+            log_index: Some(ethers::types::U256::MAX),
+            ..transfer_log()
+        };
+        let result: Result<alloy::rpc::types::Log, _> = log.try_to_alloy();
+        assert_eq!(
+            result.unwrap_err(),
+            ConversionError::Overflow {
+                from_bits: 256,
+                to_bits: 64
+            }
+        );
+    }
+
+    #[test]
+    fn test_receipt_round_trip() {
+        let receipt = ethers::types::TransactionReceipt {
+            transaction_hash: H256::repeat_byte(0x55),
+            transaction_index: U64::from(2),
+            block_hash: Some(H256::repeat_byte(0x44)),
+            block_number: Some(U64::from(100)),
+            from: H160::repeat_byte(0x22),
+            to: Some(H160::repeat_byte(0x11)),
+            cumulative_gas_used: ethers::types::U256::from(210000),
+            gas_used: Some(ethers::types::U256::from(52000)),
+            contract_address: None,
+            logs: vec![transfer_log()],
+            status: Some(U64::one()),
+            root: None,
+            logs_bloom: ethers::types::Bloom::repeat_byte(0x01),
+            transaction_type: Some(U64::from(2)),
+            effective_gas_price: Some(ethers::types::U256::from(1_000_000_000)),
+            ..Default::default()
+        };
+
+        let alloy_receipt: alloy::rpc::types::TransactionReceipt =
+            receipt.clone().try_to_alloy().unwrap();
+        assert!(alloy_receipt.inner.is_success());
+        assert!(matches!(alloy_receipt.inner, ReceiptEnvelope::Eip1559(_)));
+        assert_eq!(alloy_receipt.gas_used, 52000);
+        assert_eq!(alloy_receipt.effective_gas_price, 1_000_000_000);
+        assert_eq!(alloy_receipt.inner.cumulative_gas_used(), 210000);
+        assert_eq!(alloy_receipt.from, Address::repeat_byte(0x22));
+
+        let transfer = alloy_receipt.inner.logs()[0]
+            .log_decode::<Transfer>()
+            .unwrap()
+            .inner
+            .data;
+        assert_eq!(transfer.value, U256::from(1000));
+
+        assert_eq!(alloy_receipt.to_ethers(), receipt);
+    }
+
+    #[test]
+    fn test_receipt_failed_legacy() {
+        let receipt = ethers::types::TransactionReceipt {
+            status: Some(U64::zero()),
+            transaction_type: None,
+            gas_used: Some(ethers::types::U256::from(21000)),
+            effective_gas_price: Some(ethers::types::U256::from(1_000_000_000)),
+            ..Default::default()
+        };
+        let alloy_receipt: alloy::rpc::types::TransactionReceipt = receipt.try_to_alloy().unwrap();
+        assert!(!alloy_receipt.inner.is_success());
+        assert!(matches!(alloy_receipt.inner, ReceiptEnvelope::Legacy(_)));
+
+        let receipt = alloy_receipt.to_ethers();
+        assert_eq!(receipt.status, Some(U64::zero()));
+        assert_eq!(receipt.transaction_type, Some(U64::zero()));
+    }
+
+    #[test]
+    fn test_receipt_pre_byzantium() {
+        let receipt = ethers::types::TransactionReceipt {
+            status: None,
+            root: Some(H256::repeat_byte(0x66)),
+            transaction_type: None,
+            gas_used: Some(ethers::types::U256::from(21000)),
+            effective_gas_price: Some(ethers::types::U256::from(1_000_000_000)),
+            ..Default::default()
+        };
+        let alloy_receipt: alloy::rpc::types::TransactionReceipt =
+            receipt.clone().try_to_alloy().unwrap();
+        let ReceiptEnvelope::Legacy(ref inner) = alloy_receipt.inner else {
+            panic!("expected legacy receipt");
+        };
+        assert_eq!(
+            inner.receipt.status,
+            Eip658Value::PostState(alloy::primitives::B256::repeat_byte(0x66))
+        );
+
+        let ethers_receipt = alloy_receipt.to_ethers();
+        assert_eq!(ethers_receipt.status, None);
+        assert_eq!(ethers_receipt.root, receipt.root);
+    }
+
+    #[test]
+    fn test_receipt_missing_gas() {
+        let receipt = ethers::types::TransactionReceipt {
+            status: Some(U64::one()),
+            effective_gas_price: Some(ethers::types::U256::from(1_000_000_000)),
+            ..Default::default()
+        };
+        let result: Result<alloy::rpc::types::TransactionReceipt, _> =
+            receipt.clone().try_to_alloy();
+        assert_eq!(
+            result.unwrap_err(),
+            ConversionError::MissingField("gas_used".to_string())
+        );
+
+        let receipt = ethers::types::TransactionReceipt {
+            gas_used: Some(ethers::types::U256::from(21000)),
+            effective_gas_price: None,
+            ..receipt
+        };
+        let result: Result<alloy::rpc::types::TransactionReceipt, _> = receipt.try_to_alloy();
+        assert_eq!(
+            result.unwrap_err(),
+            ConversionError::MissingField("effective_gas_price".to_string())
+        );
+    }
+
+    #[test]
+    fn test_receipt_unsupported_type() {
+        let receipt = ethers::types::TransactionReceipt {
+            transaction_type: Some(U64::from(0x7e)),
+            ..Default::default()
+        };
+        let result: Result<alloy::rpc::types::TransactionReceipt, _> = receipt.try_to_alloy();
+        assert_eq!(
+            result.unwrap_err(),
+            ConversionError::UnsupportedTransactionType(0x7e)
+        );
+    }
+}
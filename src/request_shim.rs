@@ -1,30 +1,101 @@
 use super::*;
-use alloy::primitives::{Address, Bytes, U256, U64};
-use ethers::types::Eip1559TransactionRequest;
+use alloy::primitives::{Address, Bytes, TxKind, U256, U64};
+use alloy::rpc::types::{AccessList, TransactionInput};
+use ethers::types::transaction::eip2718::TypedTransaction;
+use ethers::types::{
+    Eip1559TransactionRequest, Eip2930TransactionRequest, NameOrAddress, TransactionRequest,
+};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::authorization::SignedAuthorization;
+#[cfg(feature = "eip4844")]
+use crate::blob::BlobSidecarBuilder;
+#[cfg(feature = "eip4844")]
+use alloy::eips::eip4844::BlobTransactionSidecar;
+#[cfg(feature = "eip4844")]
+use alloy::primitives::B256;
+
+/// Type of the transaction a request is turned into, chains that reject
+/// EIP-1559 transactions need [TransactionKind::Legacy] or
+/// [TransactionKind::Eip2930]
+#[derive(Clone, Copy, Default, PartialEq, Eq, Debug)]
+pub enum TransactionKind {
+    Legacy,
+    Eip2930,
+    #[default]
+    Eip1559,
+}
+
 pub trait TransactionRequestShim {
     fn to_eip1559(&self) -> Eip1559TransactionRequest;
+
+    fn to_legacy(&self) -> TransactionRequest;
+
+    fn to_eip2930(&self) -> Eip2930TransactionRequest;
+
+    fn to_typed(&self, kind: TransactionKind) -> TypedTransaction {
+        match kind {
+            TransactionKind::Legacy => TypedTransaction::Legacy(self.to_legacy()),
+            TransactionKind::Eip2930 => TypedTransaction::Eip2930(self.to_eip2930()),
+            TransactionKind::Eip1559 => TypedTransaction::Eip1559(self.to_eip1559()),
+        }
+    }
 }
 
-/// Parameters for sending a transaction
+/// A problem with a transaction request that would make the node reject it
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum TxValidationError {
+    #[error("max priority fee per gas {max_priority_fee_per_gas} is above max fee per gas {max_fee_per_gas}")]
+    PriorityFeeAboveMaxFee {
+        max_priority_fee_per_gas: U256,
+        max_fee_per_gas: U256,
+    },
+    #[error("gas {gas} is above the block gas limit {block_gas_limit}")]
+    GasAboveBlockLimit { gas: U256, block_gas_limit: U256 },
+    #[error("contract creation without any code, missing `to` with empty data")]
+    EmptyContractCreation,
+    #[error("chain id {actual} does not match the expected chain id {expected}")]
+    ChainIdMismatch { expected: U64, actual: U64 },
+}
+
+/// Chain values a transaction request is validated against, values that are
+/// not set are not checked
 #[derive(Clone, Default, PartialEq, Eq, Debug)]
+pub struct ValidationContext {
+    pub chain_id: Option<U64>,
+    pub block_gas_limit: Option<U256>,
+}
+
+/// Parameters for sending a transaction, (de)serialized as the JSON-RPC
+/// transaction object of `eth_sendTransaction`, with `data` accepted along
+/// or instead of `input`
+#[derive(Clone, Default, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct AlloyTransactionRequest {
     /// Sender address or ENS name
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub from: Option<Address>,
 
     /// Recipient address (None for contract creation)
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub to: Option<Address>,
 
     /// Supplied gas (None for sensible default)
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub gas: Option<U256>,
 
     /// Transferred value (None for no transfer)
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub value: Option<U256>,
 
     /// The compiled code of a contract OR the first 4 bytes of the hash of the
     /// invoked method signature and encoded parameters. For details see Ethereum Contract ABI
+    #[serde(flatten, with = "input_or_data")]
     pub data: Option<Bytes>,
 
     /// Transaction nonce (None for next available nonce)
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub nonce: Option<U256>,
 
     /// Represents the maximum tx fee that will go to the miner as part of the user's
@@ -37,15 +108,73 @@ pub struct AlloyTransactionRequest {
     ///    priority fee.
     ///
     /// More context [here](https://hackmd.io/@q8X_WM2nTfu6nuvAzqXiTQ/1559-wallets)
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub max_priority_fee_per_gas: Option<U256>,
 
     /// Represents the maximum amount that a user is willing to pay for their tx (inclusive of
     /// baseFeePerGas and maxPriorityFeePerGas). The difference between maxFeePerGas and
     /// baseFeePerGas + maxPriorityFeePerGas is “refunded” to the user.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub max_fee_per_gas: Option<U256>,
 
+    /// Gas price of legacy and EIP-2930 transactions (None for sensible default)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gas_price: Option<U256>,
+
     /// Chain ID (None for mainnet)
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub chain_id: Option<U64>,
+
+    /// EIP-2930 list of addresses and storage keys the transaction plans to
+    /// access (None for an empty list)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub access_list: Option<AccessList>,
+
+    /// EIP-7702 authorizations of accounts delegating their code, only sent
+    /// with [AlloyTransactionRequest::sign_eip7702]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub authorization_list: Option<Vec<SignedAuthorization>>,
+
+    /// Maximum fee per blob gas of EIP-4844 transactions
+    #[cfg(feature = "eip4844")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_fee_per_blob_gas: Option<U256>,
+
+    /// Versioned hashes of the blobs of EIP-4844 transactions
+    #[cfg(feature = "eip4844")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub blob_versioned_hashes: Option<Vec<B256>>,
+
+    /// Blobs with their commitments and proofs of EIP-4844 transactions
+    #[cfg(feature = "eip4844")]
+    #[serde(default, flatten, skip_serializing_if = "Option::is_none")]
+    pub sidecar: Option<BlobTransactionSidecar>,
+}
+
+/// (De)serializes the call data as `input`, also accepting the legacy `data`
+/// key, which has to hold the same value if both are sent
+mod input_or_data {
+    use super::*;
+    use serde::{de::Error, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(
+        data: &Option<Bytes>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        TransactionInput {
+            input: data.clone(),
+            data: None,
+        }
+        .serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<Bytes>, D::Error> {
+        TransactionInput::deserialize(deserializer)?
+            .try_into_unique_input()
+            .map_err(D::Error::custom)
+    }
 }
 
 impl AlloyTransactionRequest {
@@ -88,6 +217,12 @@ impl AlloyTransactionRequest {
         self
     }
 
+    /// Sets the `gas_price` field in the transaction to the provided value
+    pub fn with_gas_price<T: Into<U256>>(mut self, gas_price: Option<T>) -> Self {
+        self.gas_price = gas_price.map(|val| val.into());
+        self
+    }
+
     /// Sets the `value` field in the transaction to the provided value
     pub fn with_value<T: Into<U256>>(mut self, value: Option<T>) -> Self {
         self.value = value.map(|val| val.into());
@@ -111,6 +246,115 @@ impl AlloyTransactionRequest {
         self.chain_id = chain_id.map(|val| val.into());
         self
     }
+
+    /// Sets the `access_list` field in the transaction to the provided value
+    pub fn with_access_list<T: Into<AccessList>>(mut self, access_list: Option<T>) -> Self {
+        self.access_list = access_list.map(|val| val.into());
+        self
+    }
+
+    /// Sets the `authorization_list` field in the transaction to the provided value
+    pub fn with_authorization_list(
+        mut self,
+        authorization_list: Option<Vec<SignedAuthorization>>,
+    ) -> Self {
+        self.authorization_list = authorization_list;
+        self
+    }
+
+    /// Sets the `max_fee_per_blob_gas` field in the transaction to the provided value
+    #[cfg(feature = "eip4844")]
+    pub fn with_max_fee_per_blob_gas<T: Into<U256>>(
+        mut self,
+        max_fee_per_blob_gas: Option<T>,
+    ) -> Self {
+        self.max_fee_per_blob_gas = max_fee_per_blob_gas.map(|val| val.into());
+        self
+    }
+
+    /// Sets the `blob_versioned_hashes` field in the transaction to the provided value
+    #[cfg(feature = "eip4844")]
+    pub fn with_blob_versioned_hashes(mut self, blob_versioned_hashes: Option<Vec<B256>>) -> Self {
+        self.blob_versioned_hashes = blob_versioned_hashes;
+        self
+    }
+
+    /// Sets the `sidecar` field in the transaction to the built sidecar and
+    /// the `blob_versioned_hashes` field to the hashes of its commitments
+    #[cfg(feature = "eip4844")]
+    pub fn with_blob_sidecar(mut self, sidecar: BlobSidecarBuilder) -> Self {
+        self.blob_versioned_hashes = Some(sidecar.versioned_hashes());
+        self.sidecar = Some(sidecar.build());
+        self
+    }
+
+    /// Converts into an alloy EIP-4844 transaction request, which cannot
+    /// create contracts so errors if `to` is not set, and also errors if the
+    /// blob versioned hashes or the max fee per blob gas are not set
+    #[cfg(feature = "eip4844")]
+    pub fn to_eip4844(&self) -> Result<alloy::rpc::types::TransactionRequest, ConversionError> {
+        if self.to.is_none() {
+            return Err(ConversionError::MissingField("to".to_string()));
+        }
+        if self.blob_versioned_hashes.is_none() {
+            return Err(ConversionError::MissingField(
+                "blob_versioned_hashes".to_string(),
+            ));
+        }
+        if self.max_fee_per_blob_gas.is_none() {
+            return Err(ConversionError::MissingField(
+                "max_fee_per_blob_gas".to_string(),
+            ));
+        }
+        Ok(alloy::rpc::types::TransactionRequest {
+            transaction_type: Some(3),
+            ..alloy::rpc::types::TransactionRequest::try_from(self.clone())?
+        })
+    }
+
+    /// Checks the request for problems that do not depend on the chain, an
+    /// empty list means the request is valid
+    pub fn validate(&self) -> Vec<TxValidationError> {
+        self.validate_against(&ValidationContext::default())
+    }
+
+    /// Checks the request for problems, including the ones against the given
+    /// chain values, an empty list means the request is valid
+    pub fn validate_against(&self, context: &ValidationContext) -> Vec<TxValidationError> {
+        let mut errors = vec![];
+
+        if let (Some(max_priority_fee_per_gas), Some(max_fee_per_gas)) =
+            (self.max_priority_fee_per_gas, self.max_fee_per_gas)
+        {
+            if max_priority_fee_per_gas > max_fee_per_gas {
+                errors.push(TxValidationError::PriorityFeeAboveMaxFee {
+                    max_priority_fee_per_gas,
+                    max_fee_per_gas,
+                });
+            }
+        }
+
+        if let (Some(gas), Some(block_gas_limit)) = (self.gas, context.block_gas_limit) {
+            if gas > block_gas_limit {
+                errors.push(TxValidationError::GasAboveBlockLimit {
+                    gas,
+                    block_gas_limit,
+                });
+            }
+        }
+
+        if self.to.is_none() && self.data.as_ref().map_or(true, |data| data.is_empty()) {
+            errors.push(TxValidationError::EmptyContractCreation);
+        }
+
+        if let (Some(actual), Some(expected)) = (self.chain_id, context.chain_id) {
+            if actual != expected {
+                errors.push(TxValidationError::ChainIdMismatch { expected, actual });
+            }
+        }
+
+        errors
+    }
 }
 
 impl TransactionRequestShim for AlloyTransactionRequest {
@@ -127,15 +371,232 @@ impl TransactionRequestShim for AlloyTransactionRequest {
         tx.value = self.value.map(alloy_u256_to_ethers);
         tx.data = self.data.clone().map(alloy_bytes_to_ethers);
         tx.chain_id = self.chain_id.map(alloy_u64_to_ethers);
+        tx.access_list = self.access_list.clone().unwrap_or_default().to_ethers();
         tx
     }
+
+    fn to_legacy(&self) -> TransactionRequest {
+        let mut tx = TransactionRequest::new();
+        tx.to = self
+            .to
+            .map(|address| ethers::types::NameOrAddress::from(alloy_address_to_ethers(address)));
+        tx.from = self.from.map(alloy_address_to_ethers);
+        tx.gas = self.gas.map(alloy_u256_to_ethers);
+        tx.gas_price = self.gas_price.map(alloy_u256_to_ethers);
+        tx.nonce = self.nonce.map(alloy_u256_to_ethers);
+        tx.value = self.value.map(alloy_u256_to_ethers);
+        tx.data = self.data.clone().map(alloy_bytes_to_ethers);
+        tx.chain_id = self.chain_id.map(alloy_u64_to_ethers);
+        tx
+    }
+
+    fn to_eip2930(&self) -> Eip2930TransactionRequest {
+        Eip2930TransactionRequest::new(
+            self.to_legacy(),
+            self.access_list.clone().unwrap_or_default().to_ethers(),
+        )
+    }
+}
+
+fn resolved_address(to: Option<NameOrAddress>) -> Result<Option<Address>, ConversionError> {
+    match to {
+        Some(NameOrAddress::Address(address)) => Ok(Some(address.to_alloy())),
+        Some(NameOrAddress::Name(name)) => Err(ConversionError::UnresolvedEnsName(name)),
+        None => Ok(None),
+    }
+}
+
+/// An empty access list is taken as no access list, as that is what
+/// [TransactionRequestShim] emits for it
+fn non_empty_access_list(
+    access_list: ethers::types::transaction::eip2930::AccessList,
+) -> Option<AccessList> {
+    Some(access_list.to_alloy()).filter(|access_list| !access_list.0.is_empty())
+}
+
+/// Errors if the recipient is an ENS name that has not been resolved yet
+impl TryFrom<TransactionRequest> for AlloyTransactionRequest {
+    type Error = ConversionError;
+
+    fn try_from(tx: TransactionRequest) -> Result<Self, Self::Error> {
+        Ok(Self {
+            from: tx.from.to_alloy(),
+            to: resolved_address(tx.to)?,
+            gas: tx.gas.to_alloy(),
+            gas_price: tx.gas_price.to_alloy(),
+            value: tx.value.to_alloy(),
+            data: tx.data.to_alloy(),
+            nonce: tx.nonce.to_alloy(),
+            chain_id: tx.chain_id.to_alloy(),
+            ..Default::default()
+        })
+    }
+}
+
+/// Errors if the recipient is an ENS name that has not been resolved yet
+impl TryFrom<Eip2930TransactionRequest> for AlloyTransactionRequest {
+    type Error = ConversionError;
+
+    fn try_from(tx: Eip2930TransactionRequest) -> Result<Self, Self::Error> {
+        Ok(Self {
+            access_list: non_empty_access_list(tx.access_list),
+            ..Self::try_from(tx.tx)?
+        })
+    }
+}
+
+/// Errors if the recipient is an ENS name that has not been resolved yet
+impl TryFrom<Eip1559TransactionRequest> for AlloyTransactionRequest {
+    type Error = ConversionError;
+
+    fn try_from(tx: Eip1559TransactionRequest) -> Result<Self, Self::Error> {
+        Ok(Self {
+            from: tx.from.to_alloy(),
+            to: resolved_address(tx.to)?,
+            gas: tx.gas.to_alloy(),
+            value: tx.value.to_alloy(),
+            data: tx.data.to_alloy(),
+            nonce: tx.nonce.to_alloy(),
+            max_priority_fee_per_gas: tx.max_priority_fee_per_gas.to_alloy(),
+            max_fee_per_gas: tx.max_fee_per_gas.to_alloy(),
+            chain_id: tx.chain_id.to_alloy(),
+            access_list: non_empty_access_list(tx.access_list),
+            ..Default::default()
+        })
+    }
+}
+
+/// Errors if the recipient is an ENS name that has not been resolved yet
+impl TryFrom<&TypedTransaction> for AlloyTransactionRequest {
+    type Error = ConversionError;
+
+    fn try_from(tx: &TypedTransaction) -> Result<Self, Self::Error> {
+        match tx.clone() {
+            TypedTransaction::Legacy(tx) => Self::try_from(tx),
+            TypedTransaction::Eip2930(tx) => Self::try_from(tx),
+            TypedTransaction::Eip1559(tx) => Self::try_from(tx),
+        }
+    }
+}
+
+fn narrow<T: TryFrom<U256>>(
+    value: Option<U256>,
+    to_bits: usize,
+) -> Result<Option<T>, ConversionError> {
+    value
+        .map(|value| {
+            T::try_from(value).map_err(|_| ConversionError::Overflow {
+                from_bits: 256,
+                to_bits,
+            })
+        })
+        .transpose()
+}
+
+/// Errors if a gas, fee or nonce value does not fit the narrower alloy type,
+/// or if an EIP-7702 authorization list is set as the alloy request cannot
+/// hold it
+impl TryFrom<AlloyTransactionRequest> for alloy::rpc::types::TransactionRequest {
+    type Error = ConversionError;
+
+    fn try_from(tx: AlloyTransactionRequest) -> Result<Self, Self::Error> {
+        if tx.authorization_list.is_some() {
+            return Err(ConversionError::UnsupportedTransactionType(4));
+        }
+
+        Ok(Self {
+            from: tx.from,
+            to: tx.to.map(TxKind::Call),
+            gas: narrow(tx.gas, 128)?,
+            gas_price: narrow(tx.gas_price, 128)?,
+            max_fee_per_gas: narrow(tx.max_fee_per_gas, 128)?,
+            max_priority_fee_per_gas: narrow(tx.max_priority_fee_per_gas, 128)?,
+            value: tx.value,
+            input: tx.data.map(TransactionInput::new).unwrap_or_default(),
+            nonce: narrow(tx.nonce, 64)?,
+            chain_id: tx.chain_id.map(|chain_id| chain_id.to()),
+            access_list: tx.access_list,
+            #[cfg(feature = "eip4844")]
+            max_fee_per_blob_gas: narrow(tx.max_fee_per_blob_gas, 128)?,
+            #[cfg(feature = "eip4844")]
+            blob_versioned_hashes: tx.blob_versioned_hashes,
+            #[cfg(feature = "eip4844")]
+            sidecar: tx.sidecar,
+            ..Default::default()
+        })
+    }
+}
+
+/// Whether the fields set on the request can be sent as the given EIP-2718
+/// transaction type
+fn fields_match_type(tx: &alloy::rpc::types::TransactionRequest, transaction_type: u8) -> bool {
+    let has_blob_fields = tx.blob_versioned_hashes.is_some()
+        || tx.sidecar.is_some()
+        || tx.max_fee_per_blob_gas.is_some();
+    let has_eip1559_fees = tx.max_fee_per_gas.is_some() || tx.max_priority_fee_per_gas.is_some();
+    match transaction_type {
+        0 => !has_blob_fields && !has_eip1559_fees && tx.access_list.is_none(),
+        1 => !has_blob_fields && !has_eip1559_fees,
+        2 => !has_blob_fields && tx.gas_price.is_none(),
+        3 => cfg!(feature = "eip4844") && tx.gas_price.is_none(),
+        _ => false,
+    }
+}
+
+/// Errors for blob transaction requests unless the `eip4844` feature is
+/// enabled, and for a transaction type the set fields cannot be sent as, such
+/// as EIP-7702 whose authorization list the alloy request cannot hold. A
+/// matching type is not kept as it is inferred again from the fields.
+impl TryFrom<alloy::rpc::types::TransactionRequest> for AlloyTransactionRequest {
+    type Error = ConversionError;
+
+    fn try_from(tx: alloy::rpc::types::TransactionRequest) -> Result<Self, Self::Error> {
+        if let Some(transaction_type) = tx.transaction_type {
+            if !fields_match_type(&tx, transaction_type) {
+                return Err(ConversionError::UnsupportedTransactionType(
+                    transaction_type.into(),
+                ));
+            }
+        }
+
+        #[cfg(not(feature = "eip4844"))]
+        if tx.blob_versioned_hashes.is_some()
+            || tx.sidecar.is_some()
+            || tx.max_fee_per_blob_gas.is_some()
+        {
+            return Err(ConversionError::UnsupportedTransactionType(3));
+        }
+
+        Ok(Self {
+            from: tx.from,
+            to: tx.to.and_then(|to| to.to().copied()),
+            gas: tx.gas.map(U256::from),
+            gas_price: tx.gas_price.map(U256::from),
+            max_fee_per_gas: tx.max_fee_per_gas.map(U256::from),
+            max_priority_fee_per_gas: tx.max_priority_fee_per_gas.map(U256::from),
+            value: tx.value,
+            data: tx.input.input().cloned(),
+            nonce: tx.nonce.map(U256::from),
+            chain_id: tx.chain_id.map(U64::from),
+            access_list: tx.access_list,
+            authorization_list: None,
+            #[cfg(feature = "eip4844")]
+            max_fee_per_blob_gas: tx.max_fee_per_blob_gas.map(U256::from),
+            #[cfg(feature = "eip4844")]
+            blob_versioned_hashes: tx.blob_versioned_hashes,
+            #[cfg(feature = "eip4844")]
+            sidecar: tx.sidecar,
+        })
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use ethers::types::transaction::eip2930::AccessList;
+    use ethers::types::transaction::eip2930;
 
     use super::*;
+    use alloy::primitives::B256;
+    use alloy::rpc::types::AccessListItem;
     #[test]
     fn test_to_eip1559() {
         let request = AlloyTransactionRequest {
@@ -147,7 +608,16 @@ mod tests {
             nonce: Some(U256::from(0)),
             max_priority_fee_per_gas: Some(U256::from(100)),
             max_fee_per_gas: Some(U256::from(200)),
+            gas_price: None,
             chain_id: Some(U64::from(1)),
+            access_list: None,
+            authorization_list: None,
+            #[cfg(feature = "eip4844")]
+            max_fee_per_blob_gas: None,
+            #[cfg(feature = "eip4844")]
+            blob_versioned_hashes: None,
+            #[cfg(feature = "eip4844")]
+            sidecar: None,
         };
 
         let expected = Eip1559TransactionRequest {
@@ -160,7 +630,7 @@ mod tests {
             data: Some(ethers::types::Bytes::from(vec![1, 2, 3])),
             nonce: Some(ethers::types::U256::from(0)),
             max_priority_fee_per_gas: Some(ethers::types::U256::from(100)),
-            access_list: AccessList::default(),
+            access_list: eip2930::AccessList::default(),
             max_fee_per_gas: Some(ethers::types::U256::from(200)),
             chain_id: Some(ethers::types::U64::from(1)),
         };
@@ -179,7 +649,9 @@ mod tests {
             .with_nonce(Some(U256::from(0)))
             .with_max_priority_fee_per_gas(Some(U256::from(100)))
             .with_max_fee_per_gas(Some(U256::from(200)))
-            .with_chain_id(Some(U64::from(1)));
+            .with_gas_price(Some(U256::from(300)))
+            .with_chain_id(Some(U64::from(1)))
+            .with_access_list(Some(access_list()));
 
         assert_eq!(request.from, Some(Address::repeat_byte(1)));
         assert_eq!(request.to, Some(Address::repeat_byte(2)));
@@ -189,6 +661,479 @@ mod tests {
         assert_eq!(request.nonce, Some(U256::from(0)));
         assert_eq!(request.max_priority_fee_per_gas, Some(U256::from(100)));
         assert_eq!(request.max_fee_per_gas, Some(U256::from(200)));
+        assert_eq!(request.gas_price, Some(U256::from(300)));
         assert_eq!(request.chain_id, Some(U64::from(1)));
+        assert_eq!(request.access_list, Some(access_list()));
+    }
+
+    fn access_list() -> AccessList {
+        AccessList(vec![AccessListItem {
+            address: Address::repeat_byte(3),
+            storage_keys: vec![B256::with_last_byte(1), B256::with_last_byte(2)],
+        }])
+    }
+
+    #[test]
+    fn test_to_eip1559_access_list() {
+        let request = AlloyTransactionRequest::new()
+            .with_to(Some(Address::repeat_byte(2)))
+            .with_access_list(Some(access_list()));
+
+        let tx = request.to_eip1559();
+        assert_eq!(
+            tx.access_list,
+            eip2930::AccessList::from(vec![eip2930::AccessListItem {
+                address: ethers::types::H160::repeat_byte(3),
+                storage_keys: vec![
+                    ethers::types::H256::from_low_u64_be(1),
+                    ethers::types::H256::from_low_u64_be(2)
+                ],
+            }])
+        );
+        assert_eq!(tx.access_list.to_alloy(), access_list());
+    }
+
+    #[test]
+    fn test_to_legacy() {
+        let request = AlloyTransactionRequest::new()
+            .with_to(Some(Address::repeat_byte(2)))
+            .with_from(Some(Address::repeat_byte(1)))
+            .with_gas(Some(U256::from(100000)))
+            .with_gas_price(Some(U256::from(300)))
+            .with_value(Some(U256::from(12345)))
+            .with_data(Some(vec![1, 2, 3]))
+            .with_nonce(Some(U256::from(0)))
+            .with_max_fee_per_gas(Some(U256::from(200)))
+            .with_chain_id(Some(U64::from(1)));
+
+        let expected = TransactionRequest {
+            to: Some(ethers::types::NameOrAddress::Address(ethers::types::H160(
+                [2; 20],
+            ))),
+            from: Some(ethers::types::H160([1; 20])),
+            gas: Some(ethers::types::U256::from(100000)),
+            gas_price: Some(ethers::types::U256::from(300)),
+            value: Some(ethers::types::U256::from(12345)),
+            data: Some(ethers::types::Bytes::from(vec![1, 2, 3])),
+            nonce: Some(ethers::types::U256::from(0)),
+            chain_id: Some(ethers::types::U64::from(1)),
+        };
+
+        assert_eq!(request.to_legacy(), expected);
+    }
+
+    #[test]
+    fn test_to_eip2930() {
+        let request = AlloyTransactionRequest::new()
+            .with_to(Some(Address::repeat_byte(2)))
+            .with_gas_price(Some(U256::from(300)))
+            .with_access_list(Some(access_list()));
+
+        let tx = request.to_eip2930();
+        assert_eq!(tx.tx, request.to_legacy());
+        assert_eq!(tx.access_list, access_list().to_ethers());
+    }
+
+    #[test]
+    fn test_to_typed() {
+        let request = AlloyTransactionRequest::new()
+            .with_to(Some(Address::repeat_byte(2)))
+            .with_gas_price(Some(U256::from(300)))
+            .with_max_fee_per_gas(Some(U256::from(200)));
+
+        assert_eq!(
+            request.to_typed(TransactionKind::Legacy),
+            TypedTransaction::Legacy(request.to_legacy())
+        );
+        assert_eq!(
+            request.to_typed(TransactionKind::Eip2930),
+            TypedTransaction::Eip2930(request.to_eip2930())
+        );
+        assert_eq!(
+            request.to_typed(TransactionKind::default()),
+            TypedTransaction::Eip1559(request.to_eip1559())
+        );
+    }
+
+    #[test]
+    fn test_from_typed_transaction_round_trip() {
+        let request = AlloyTransactionRequest::new()
+            .with_to(Some(Address::repeat_byte(2)))
+            .with_from(Some(Address::repeat_byte(1)))
+            .with_gas(Some(U256::from(100000)))
+            .with_value(Some(U256::from(12345)))
+            .with_data(Some(vec![1, 2, 3]))
+            .with_nonce(Some(U256::from(7)))
+            .with_chain_id(Some(U64::from(1)));
+
+        let legacy = request.clone().with_gas_price(Some(U256::from(300)));
+        let tx = legacy.to_typed(TransactionKind::Legacy);
+        assert_eq!(AlloyTransactionRequest::try_from(&tx).unwrap(), legacy);
+
+        let eip2930 = legacy.with_access_list(Some(access_list()));
+        let tx = eip2930.to_typed(TransactionKind::Eip2930);
+        assert_eq!(AlloyTransactionRequest::try_from(&tx).unwrap(), eip2930);
+
+        let eip1559 = request
+            .with_max_fee_per_gas(Some(U256::from(200)))
+            .with_max_priority_fee_per_gas(Some(U256::from(100)));
+        let tx = eip1559.to_typed(TransactionKind::Eip1559);
+        assert_eq!(AlloyTransactionRequest::try_from(&tx).unwrap(), eip1559);
+
+        let eip1559 = eip1559.with_access_list(Some(access_list()));
+        assert_eq!(
+            AlloyTransactionRequest::try_from(eip1559.to_eip1559()).unwrap(),
+            eip1559
+        );
+    }
+
+    #[test]
+    fn test_from_typed_transaction_unresolved_ens() {
+        let tx = TypedTransaction::Eip1559(
+            Eip1559TransactionRequest::new().to(NameOrAddress::Name("vitalik.eth".to_string())),
+        );
+        assert_eq!(
+            AlloyTransactionRequest::try_from(&tx).unwrap_err(),
+            ConversionError::UnresolvedEnsName("vitalik.eth".to_string())
+        );
+
+        let tx = TransactionRequest::new().to(NameOrAddress::Name("vitalik.eth".to_string()));
+        assert!(AlloyTransactionRequest::try_from(tx).is_err());
+    }
+
+    #[test]
+    fn test_rpc_transaction_request_round_trip() {
+        let request = AlloyTransactionRequest::new()
+            .with_to(Some(Address::repeat_byte(2)))
+            .with_from(Some(Address::repeat_byte(1)))
+            .with_gas(Some(U256::from(100000)))
+            .with_gas_price(Some(U256::from(300)))
+            .with_max_fee_per_gas(Some(U256::from(200)))
+            .with_max_priority_fee_per_gas(Some(U256::from(100)))
+            .with_value(Some(U256::from(12345)))
+            .with_data(Some(vec![1, 2, 3]))
+            .with_nonce(Some(U256::from(7)))
+            .with_chain_id(Some(U64::from(1)))
+            .with_access_list(Some(access_list()));
+
+        let rpc_request = alloy::rpc::types::TransactionRequest::try_from(request.clone()).unwrap();
+        assert_eq!(rpc_request.to, Some(TxKind::Call(Address::repeat_byte(2))));
+        assert_eq!(rpc_request.gas, Some(100000));
+        assert_eq!(rpc_request.gas_price, Some(300));
+        assert_eq!(rpc_request.nonce, Some(7));
+        assert_eq!(rpc_request.chain_id, Some(1));
+        assert_eq!(rpc_request.input.input(), Some(&Bytes::from(vec![1, 2, 3])));
+        assert_eq!(rpc_request.access_list, Some(access_list()));
+
+        assert_eq!(
+            AlloyTransactionRequest::try_from(rpc_request).unwrap(),
+            request
+        );
+        assert_eq!(
+            AlloyTransactionRequest::try_from(alloy::rpc::types::TransactionRequest::default())
+                .unwrap(),
+            AlloyTransactionRequest::default()
+        );
+    }
+
+    #[test]
+    fn test_rpc_transaction_request_legacy_data_field() {
+        let rpc_request = alloy::rpc::types::TransactionRequest {
+            to: Some(TxKind::Create),
+            input: TransactionInput {
+                input: None,
+                data: Some(Bytes::from(vec![4, 5, 6])),
+            },
+            ..Default::default()
+        };
+        let request = AlloyTransactionRequest::try_from(rpc_request).unwrap();
+        assert_eq!(request.to, None);
+        assert_eq!(request.data, Some(Bytes::from(vec![4, 5, 6])));
+    }
+
+    #[test]
+    fn test_rpc_transaction_request_errors() {
+        let request = AlloyTransactionRequest::new().with_nonce(Some(U256::from(u128::MAX)));
+        assert_eq!(
+            alloy::rpc::types::TransactionRequest::try_from(request).unwrap_err(),
+            ConversionError::Overflow {
+                from_bits: 256,
+                to_bits: 64
+            }
+        );
+
+        let request = AlloyTransactionRequest::new().with_gas(Some(U256::MAX));
+        assert_eq!(
+            alloy::rpc::types::TransactionRequest::try_from(request).unwrap_err(),
+            ConversionError::Overflow {
+                from_bits: 256,
+                to_bits: 128
+            }
+        );
+    }
+
+    #[test]
+    fn test_rpc_transaction_request_type() {
+        let rpc_request = alloy::rpc::types::TransactionRequest {
+            max_fee_per_gas: Some(200),
+            transaction_type: Some(2),
+            ..Default::default()
+        };
+        let request = AlloyTransactionRequest::try_from(rpc_request).unwrap();
+        assert_eq!(request.max_fee_per_gas, Some(U256::from(200)));
+
+        // a type the fields cannot be sent as is not silently dropped
+        let rpc_request = alloy::rpc::types::TransactionRequest {
+            max_fee_per_gas: Some(200),
+            transaction_type: Some(0),
+            ..Default::default()
+        };
+        assert_eq!(
+            AlloyTransactionRequest::try_from(rpc_request).unwrap_err(),
+            ConversionError::UnsupportedTransactionType(0)
+        );
+
+        let rpc_request = alloy::rpc::types::TransactionRequest {
+            transaction_type: Some(4),
+            ..Default::default()
+        };
+        assert_eq!(
+            AlloyTransactionRequest::try_from(rpc_request).unwrap_err(),
+            ConversionError::UnsupportedTransactionType(4)
+        );
+    }
+
+    #[test]
+    fn test_rpc_transaction_request_authorization_list() {
+        let authorization = crate::authorization::Authorization {
+            chain_id: U256::from(1),
+            address: Address::repeat_byte(0x33),
+            nonce: U64::ZERO,
+        }
+        .sign(&ethers::signers::LocalWallet::new(
+            &mut ethers::core::rand::thread_rng(),
+        ))
+        .unwrap();
+        let request =
+            AlloyTransactionRequest::new().with_authorization_list(Some(vec![authorization]));
+        assert_eq!(
+            alloy::rpc::types::TransactionRequest::try_from(request).unwrap_err(),
+            ConversionError::UnsupportedTransactionType(4)
+        );
+    }
+
+    #[cfg(not(feature = "eip4844"))]
+    #[test]
+    fn test_rpc_transaction_request_blob_unsupported() {
+        let rpc_request = alloy::rpc::types::TransactionRequest {
+            blob_versioned_hashes: Some(vec![B256::with_last_byte(1)]),
+            ..Default::default()
+        };
+        assert_eq!(
+            AlloyTransactionRequest::try_from(rpc_request).unwrap_err(),
+            ConversionError::UnsupportedTransactionType(3)
+        );
+    }
+
+    #[cfg(feature = "eip4844")]
+    #[test]
+    fn test_to_eip4844() {
+        use crate::blob::BlobSidecarBuilder;
+        use alloy::eips::eip4844::{Blob, Bytes48};
+
+        let sidecar = BlobSidecarBuilder::new().with_blob(
+            Blob::ZERO,
+            Bytes48::repeat_byte(1),
+            Bytes48::repeat_byte(2),
+        );
+        let versioned_hashes = sidecar.versioned_hashes();
+        let request = AlloyTransactionRequest::new()
+            .with_to(Some(Address::repeat_byte(2)))
+            .with_max_fee_per_gas(Some(U256::from(200)))
+            .with_max_priority_fee_per_gas(Some(U256::from(100)))
+            .with_max_fee_per_blob_gas(Some(U256::from(50)))
+            .with_blob_sidecar(sidecar);
+        assert_eq!(
+            request.blob_versioned_hashes,
+            Some(versioned_hashes.clone())
+        );
+
+        let rpc_request = request.to_eip4844().unwrap();
+        assert_eq!(rpc_request.transaction_type, Some(3));
+        assert_eq!(rpc_request.max_fee_per_blob_gas, Some(50));
+        assert_eq!(rpc_request.blob_versioned_hashes, Some(versioned_hashes));
+        assert_eq!(rpc_request.sidecar, request.sidecar);
+
+        assert_eq!(
+            AlloyTransactionRequest::try_from(rpc_request).unwrap(),
+            request
+        );
+
+        let missing_hashes = AlloyTransactionRequest {
+            blob_versioned_hashes: None,
+            ..request.clone()
+        };
+        assert_eq!(
+            missing_hashes.to_eip4844().unwrap_err(),
+            ConversionError::MissingField("blob_versioned_hashes".to_string())
+        );
+
+        let missing_blob_fee = request.clone().with_max_fee_per_blob_gas(None::<U256>);
+        assert_eq!(
+            missing_blob_fee.to_eip4844().unwrap_err(),
+            ConversionError::MissingField("max_fee_per_blob_gas".to_string())
+        );
+
+        let request = AlloyTransactionRequest {
+            to: None,
+            ..request
+        };
+        assert_eq!(
+            request.to_eip4844().unwrap_err(),
+            ConversionError::MissingField("to".to_string())
+        );
+    }
+
+    const EIP1559_REQUEST: &str = include_str!("../fixtures/eth_send_transaction_eip1559.json");
+    const LEGACY_REQUEST: &str = include_str!("../fixtures/eth_send_transaction_legacy.json");
+
+    #[test]
+    fn test_serialize_eip1559_request() {
+        let request = AlloyTransactionRequest::new()
+            .with_from(Some(Address::repeat_byte(0x11)))
+            .with_to(Some(Address::repeat_byte(0x22)))
+            .with_gas(Some(U256::from(100000)))
+            .with_value(Some(U256::from(12345)))
+            .with_data(Some(vec![1, 2, 3]))
+            .with_nonce(Some(U256::from(7)))
+            .with_max_priority_fee_per_gas(Some(U256::from(1_000_000_000)))
+            .with_max_fee_per_gas(Some(U256::from(2_000_000_000)))
+            .with_chain_id(Some(U64::from(1)))
+            .with_access_list(Some(AccessList(vec![AccessListItem {
+                address: Address::repeat_byte(0x33),
+                storage_keys: vec![B256::with_last_byte(1)],
+            }])));
+
+        let golden: serde_json::Value = serde_json::from_str(EIP1559_REQUEST).unwrap();
+        assert_eq!(serde_json::to_value(&request).unwrap(), golden);
+        assert_eq!(
+            serde_json::from_str::<AlloyTransactionRequest>(EIP1559_REQUEST).unwrap(),
+            request
+        );
+    }
+
+    #[test]
+    fn test_serialize_legacy_request() {
+        let request = AlloyTransactionRequest::new()
+            .with_from(Some(Address::repeat_byte(0x11)))
+            .with_to(Some(Address::repeat_byte(0x22)))
+            .with_gas(Some(U256::from(21000)))
+            .with_gas_price(Some(U256::from(20_000_000_000_u64)))
+            .with_value(Some(U256::ZERO))
+            .with_data(Some(Bytes::new()))
+            .with_nonce(Some(U256::ZERO))
+            .with_chain_id(Some(U64::from(137)));
+
+        let golden: serde_json::Value = serde_json::from_str(LEGACY_REQUEST).unwrap();
+        assert_eq!(serde_json::to_value(&request).unwrap(), golden);
+        assert_eq!(
+            serde_json::from_str::<AlloyTransactionRequest>(LEGACY_REQUEST).unwrap(),
+            request
+        );
+    }
+
+    #[test]
+    fn test_deserialize_data_alias() {
+        let request: AlloyTransactionRequest = serde_json::from_str(
+            r#"{"to":"0x2222222222222222222222222222222222222222","data":"0x010203"}"#,
+        )
+        .unwrap();
+        assert_eq!(request.data, Some(Bytes::from(vec![1, 2, 3])));
+        assert_eq!(
+            serde_json::to_string(&request).unwrap(),
+            r#"{"to":"0x2222222222222222222222222222222222222222","input":"0x010203"}"#
+        );
+
+        assert_eq!(
+            serde_json::to_string(&AlloyTransactionRequest::new()).unwrap(),
+            "{}"
+        );
+    }
+
+    #[test]
+    fn test_deserialize_input_and_data() {
+        let request: AlloyTransactionRequest = serde_json::from_str(
+            r#"{"to":"0x2222222222222222222222222222222222222222","input":"0x010203","data":"0x010203"}"#,
+        )
+        .unwrap();
+        assert_eq!(request.data, Some(Bytes::from(vec![1, 2, 3])));
+        assert_eq!(
+            serde_json::to_string(&request).unwrap(),
+            r#"{"to":"0x2222222222222222222222222222222222222222","input":"0x010203"}"#
+        );
+
+        let result = serde_json::from_str::<AlloyTransactionRequest>(
+            r#"{"to":"0x2222222222222222222222222222222222222222","input":"0x010203","data":"0x040506"}"#,
+        );
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_validate() {
+        let request = AlloyTransactionRequest::new()
+            .with_to(Some(Address::repeat_byte(2)))
+            .with_gas(Some(U256::from(100000)))
+            .with_max_priority_fee_per_gas(Some(U256::from(100)))
+            .with_max_fee_per_gas(Some(U256::from(200)))
+            .with_chain_id(Some(U64::from(1)));
+        assert_eq!(request.validate(), vec![]);
+        assert_eq!(
+            request.validate_against(&ValidationContext {
+                chain_id: Some(U64::from(1)),
+                block_gas_limit: Some(U256::from(30_000_000)),
+            }),
+            vec![]
+        );
+
+        let request = AlloyTransactionRequest::new()
+            .with_gas(Some(U256::from(40_000_000)))
+            .with_max_priority_fee_per_gas(Some(U256::from(300)))
+            .with_max_fee_per_gas(Some(U256::from(200)))
+            .with_chain_id(Some(U64::from(137)));
+        assert_eq!(
+            request.validate(),
+            vec![
+                TxValidationError::PriorityFeeAboveMaxFee {
+                    max_priority_fee_per_gas: U256::from(300),
+                    max_fee_per_gas: U256::from(200),
+                },
+                TxValidationError::EmptyContractCreation,
+            ]
+        );
+        assert_eq!(
+            request.validate_against(&ValidationContext {
+                chain_id: Some(U64::from(1)),
+                block_gas_limit: Some(U256::from(30_000_000)),
+            }),
+            vec![
+                TxValidationError::PriorityFeeAboveMaxFee {
+                    max_priority_fee_per_gas: U256::from(300),
+                    max_fee_per_gas: U256::from(200),
+                },
+                TxValidationError::GasAboveBlockLimit {
+                    gas: U256::from(40_000_000),
+                    block_gas_limit: U256::from(30_000_000),
+                },
+                TxValidationError::EmptyContractCreation,
+                TxValidationError::ChainIdMismatch {
+                    expected: U64::from(1),
+                    actual: U64::from(137),
+                },
+            ]
+        );
+
+        // contract creation with code is fine
+        let request = AlloyTransactionRequest::new().with_data(Some(vec![0x60, 0x80]));
+        assert_eq!(request.validate(), vec![]);
     }
 }
@@ -1,4 +1,10 @@
-use serde::{Deserialize, Serialize};
+use crate::overrides::{BlockOverrides, StateOverride};
+use alloy::primitives::{Address, B256, U256, U64};
+use alloy::rpc::types::{BlockId, BlockNumberOrTag};
+use ethers::providers::MiddlewareError;
+use serde::de::{DeserializeOwned, Error as _};
+use serde::{Deserialize, Deserializer, Serialize};
+use serde_json::value::{to_raw_value, RawValue};
 use serde_json::Value;
 
 pub use ethers::types::transaction::*;
@@ -24,37 +30,243 @@ impl<T> Request<T> {
         }
     }
 
-    /// creates a new eth_call rpc request from the given transaction and block
+    /// creates a new eth_call rpc request from the given transaction and block,
+    /// which can be a tag, number or EIP-1898 block hash
     pub fn eth_call_request(
         id: u64,
         tx: eip2718::TypedTransaction,
-        block: Option<BlockNumber>,
-    ) -> Request<(eip2718::TypedTransaction, BlockNumber)> {
+        block: Option<BlockId>,
+    ) -> Request<(eip2718::TypedTransaction, BlockId)> {
         Request {
             id,
             jsonrpc: "2.0".to_string(),
             method: "eth_call".to_string(),
-            params: (tx, block.unwrap_or(BlockNumber::Latest)),
+            params: (
+                tx,
+                block.unwrap_or(BlockId::Number(BlockNumberOrTag::Latest)),
+            ),
         }
     }
 }
 
+impl Request<(eip2718::TypedTransaction, BlockId, StateOverride)> {
+    /// creates a new eth_call rpc request from the given transaction and block,
+    /// simulated on top of the given account state overrides
+    pub fn eth_call_with_state_override_request(
+        id: u64,
+        tx: eip2718::TypedTransaction,
+        block: Option<BlockId>,
+        state_override: StateOverride,
+    ) -> Self {
+        Request::new(
+            id,
+            "eth_call",
+            (
+                tx,
+                block.unwrap_or(BlockId::Number(BlockNumberOrTag::Latest)),
+                state_override,
+            ),
+        )
+    }
+}
+
+impl
+    Request<(
+        eip2718::TypedTransaction,
+        BlockId,
+        StateOverride,
+        BlockOverrides,
+    )>
+{
+    /// creates a new eth_call rpc request from the given transaction and block,
+    /// simulated on top of the given account state overrides (none if not set)
+    /// and in the overriden block environment
+    pub fn eth_call_with_overrides_request(
+        id: u64,
+        tx: eip2718::TypedTransaction,
+        block: Option<BlockId>,
+        state_override: Option<StateOverride>,
+        block_overrides: BlockOverrides,
+    ) -> Self {
+        Request::new(
+            id,
+            "eth_call",
+            (
+                tx,
+                block.unwrap_or(BlockId::Number(BlockNumberOrTag::Latest)),
+                state_override.unwrap_or_default(),
+                block_overrides,
+            ),
+        )
+    }
+}
+
 impl<T: Serialize> Request<T> {
     pub fn to_json_string(&self) -> Result<String, serde_json::Error> {
         serde_json::to_string(&self)
     }
 }
 
+// Typed constructors of the eth_* read methods, blocks are selected with the
+// alloy types like in `eth_call_request` and a missing one defaults to the
+// latest block
+
+impl Request<(eip2718::TypedTransaction, BlockId)> {
+    /// creates a new eth_estimateGas rpc request from the given transaction and block
+    pub fn eth_estimate_gas_request(
+        id: u64,
+        tx: eip2718::TypedTransaction,
+        block: Option<BlockId>,
+    ) -> Self {
+        Request::new(
+            id,
+            "eth_estimateGas",
+            (
+                tx,
+                block.unwrap_or(BlockId::Number(BlockNumberOrTag::Latest)),
+            ),
+        )
+    }
+
+    /// creates a new eth_createAccessList rpc request from the given transaction and block
+    pub fn eth_create_access_list_request(
+        id: u64,
+        tx: eip2718::TypedTransaction,
+        block: Option<BlockId>,
+    ) -> Self {
+        Request::new(
+            id,
+            "eth_createAccessList",
+            (
+                tx,
+                block.unwrap_or(BlockId::Number(BlockNumberOrTag::Latest)),
+            ),
+        )
+    }
+}
+
+impl Request<(ethers::types::Filter,)> {
+    /// creates a new eth_getLogs rpc request from the given filter
+    pub fn eth_get_logs_request(id: u64, filter: ethers::types::Filter) -> Self {
+        Request::new(id, "eth_getLogs", (filter,))
+    }
+}
+
+impl Request<(Address, BlockId)> {
+    /// creates a new eth_getBalance rpc request for the given account and block
+    pub fn eth_get_balance_request(id: u64, address: Address, block: Option<BlockId>) -> Self {
+        Request::new(
+            id,
+            "eth_getBalance",
+            (
+                address,
+                block.unwrap_or(BlockId::Number(BlockNumberOrTag::Latest)),
+            ),
+        )
+    }
+
+    /// creates a new eth_getCode rpc request for the given account and block
+    pub fn eth_get_code_request(id: u64, address: Address, block: Option<BlockId>) -> Self {
+        Request::new(
+            id,
+            "eth_getCode",
+            (
+                address,
+                block.unwrap_or(BlockId::Number(BlockNumberOrTag::Latest)),
+            ),
+        )
+    }
+
+    /// creates a new eth_getTransactionCount rpc request for the given account and block
+    pub fn eth_get_transaction_count_request(
+        id: u64,
+        address: Address,
+        block: Option<BlockId>,
+    ) -> Self {
+        Request::new(
+            id,
+            "eth_getTransactionCount",
+            (
+                address,
+                block.unwrap_or(BlockId::Number(BlockNumberOrTag::Latest)),
+            ),
+        )
+    }
+}
+
+impl Request<(Address, U256, BlockId)> {
+    /// creates a new eth_getStorageAt rpc request for the given account, slot and block
+    pub fn eth_get_storage_at_request(
+        id: u64,
+        address: Address,
+        slot: U256,
+        block: Option<BlockId>,
+    ) -> Self {
+        Request::new(
+            id,
+            "eth_getStorageAt",
+            (
+                address,
+                slot,
+                block.unwrap_or(BlockId::Number(BlockNumberOrTag::Latest)),
+            ),
+        )
+    }
+}
+
+impl Request<(U64, BlockNumberOrTag, Vec<f64>)> {
+    /// creates a new eth_feeHistory rpc request for the given number of blocks
+    /// up to the newest block, with the given reward percentiles
+    pub fn eth_fee_history_request(
+        id: u64,
+        block_count: u64,
+        newest_block: Option<BlockNumberOrTag>,
+        reward_percentiles: Vec<f64>,
+    ) -> Self {
+        Request::new(
+            id,
+            "eth_feeHistory",
+            (
+                U64::from(block_count),
+                newest_block.unwrap_or(BlockNumberOrTag::Latest),
+                reward_percentiles,
+            ),
+        )
+    }
+}
+
+impl Request<(BlockNumberOrTag, bool)> {
+    /// creates a new eth_getBlockByNumber rpc request, with full transaction
+    /// objects or only their hashes
+    pub fn eth_get_block_by_number_request(
+        id: u64,
+        block: BlockNumberOrTag,
+        full_transactions: bool,
+    ) -> Self {
+        Request::new(id, "eth_getBlockByNumber", (block, full_transactions))
+    }
+}
+
+impl Request<(B256, bool)> {
+    /// creates a new eth_getBlockByHash rpc request, with full transaction
+    /// objects or only their hashes
+    pub fn eth_get_block_by_hash_request(id: u64, hash: B256, full_transactions: bool) -> Self {
+        Request::new(id, "eth_getBlockByHash", (hash, full_transactions))
+    }
+}
+
 /// A JSON-RPC response, taken from ethers since it is private
 /// https://github.com/gakonst/ethers-rs/blob/master/ethers-providers/src/rpc/transports/common.rs
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+///
+/// The result defaults to raw JSON so any method can be represented, a
+/// concrete result type deserializes it directly
+#[derive(Debug, Clone, Serialize)]
 #[serde(untagged)]
-pub enum Response {
+pub enum Response<T = Box<RawValue>> {
     Success {
         jsonrpc: String,
         id: u64,
-        // hex string data
-        result: String,
+        result: T,
     },
     Error {
         jsonrpc: String,
@@ -62,16 +274,111 @@ pub enum Response {
         error: Error,
     },
 }
+
+// untagged enums buffer their content, which raw values cannot be read from,
+// so responses are told apart by their `error` field instead
+impl<'de, T: DeserializeOwned> Deserialize<'de> for Response<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        struct RawResponse<T> {
+            jsonrpc: String,
+            id: u64,
+            result: Option<T>,
+            error: Option<Error>,
+        }
+
+        let response = RawResponse::<T>::deserialize(deserializer)?;
+        match (response.error, response.result) {
+            (Some(error), _) => Ok(Response::Error {
+                jsonrpc: response.jsonrpc,
+                id: response.id,
+                error,
+            }),
+            (None, result) => Ok(Response::Success {
+                jsonrpc: response.jsonrpc,
+                id: response.id,
+                // a null result is read as a missing one
+                result: match result {
+                    Some(result) => result,
+                    None => serde_json::from_str("null").map_err(D::Error::custom)?,
+                },
+            }),
+        }
+    }
+}
+
+// raw JSON results have no equality of their own, so responses are compared
+// by their serialized JSON, which for a raw result is its text as is
+impl<T: Serialize> PartialEq for Response<T> {
+    fn eq(&self, other: &Self) -> bool {
+        match (serde_json::to_string(self), serde_json::to_string(other)) {
+            (Ok(response), Ok(other)) => response == other,
+            _ => false,
+        }
+    }
+}
+
 impl Response {
+    /// Creates a success response with a string result, such as the hex data
+    /// returned by `eth_call`
     pub fn new_success(id: u64, result_data: &str) -> Self {
+        Response::new_typed_success(
+            id,
+            // a string always serializes
+            to_raw_value(result_data).expect("string serializes to json"),
+        )
+    }
+
+    pub fn new_error(id: u64, code: i64, message: &str, data: Option<&str>) -> Self {
+        Response::new_typed_error(id, code, message, data)
+    }
+
+    /// Creates a success response with any serializable result
+    pub fn new_serialized_success<R: Serialize + ?Sized>(
+        id: u64,
+        result: &R,
+    ) -> Result<Self, serde_json::Error> {
+        Ok(Response::new_typed_success(id, to_raw_value(result)?))
+    }
+
+    /// Creates an `eth_blockNumber` response
+    pub fn new_block_number(id: u64, block_number: u64) -> Self {
+        Response::new_success(id, &format!("{:#x}", block_number))
+    }
+
+    /// Creates an `eth_getBlockByNumber` or `eth_getBlockByHash` response,
+    /// `None` for a block that is not found
+    pub fn new_block(
+        id: u64,
+        block: Option<&alloy::rpc::types::Block>,
+    ) -> Result<Self, serde_json::Error> {
+        Response::new_serialized_success(id, &block)
+    }
+
+    /// Creates an `eth_feeHistory` response
+    pub fn new_fee_history(
+        id: u64,
+        fee_history: &alloy::rpc::types::FeeHistory,
+    ) -> Result<Self, serde_json::Error> {
+        Response::new_serialized_success(id, fee_history)
+    }
+
+    /// Creates an `eth_getLogs` response
+    pub fn new_logs(id: u64, logs: &[alloy::rpc::types::Log]) -> Result<Self, serde_json::Error> {
+        Response::new_serialized_success(id, logs)
+    }
+}
+
+impl<T> Response<T> {
+    pub fn new_typed_success(id: u64, result: T) -> Self {
         Response::Success {
             id,
             jsonrpc: "2.0".to_string(),
-            result: result_data.to_string(),
+            result,
         }
     }
 
-    pub fn new_error(id: u64, code: i64, message: &str, data: Option<&str>) -> Self {
+    pub fn new_typed_error(id: u64, code: i64, message: &str, data: Option<&str>) -> Self {
         Response::Error {
             id,
             jsonrpc: "2.0".to_string(),
@@ -83,6 +390,115 @@ impl Response {
         }
     }
 
+    /// Id of the request this response answers
+    pub fn id(&self) -> u64 {
+        match self {
+            Response::Success { id, .. } | Response::Error { id, .. } => *id,
+        }
+    }
+}
+
+impl<T: Serialize> Response<T> {
+    pub fn to_json_string(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string(&self)
+    }
+}
+
+/// A JSON-RPC batch of requests, serialized as an array, the params type
+/// defaults to [Value] so a batch can mix methods
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(transparent)]
+pub struct BatchRequest<T = Value>(pub Vec<Request<T>>);
+
+impl<T> Default for BatchRequest<T> {
+    fn default() -> Self {
+        Self(vec![])
+    }
+}
+
+impl<T> BatchRequest<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a request to the batch, ids are expected to be unique in the batch
+    pub fn push(&mut self, request: Request<T>) -> &mut Self {
+        self.0.push(request);
+        self
+    }
+
+    pub fn ids(&self) -> Vec<u64> {
+        self.0.iter().map(|request| request.id).collect()
+    }
+}
+
+impl<T: Serialize> BatchRequest<T> {
+    pub fn to_json_string(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string(&self)
+    }
+}
+
+/// Failure of a single item of a batch
+#[derive(thiserror::Error, Debug, Clone, PartialEq)]
+pub enum BatchItemError {
+    #[error("request {} failed with code {}: {}", .id, .error.code, .error.message)]
+    RpcError { id: u64, error: Error },
+    #[error("no response for request {0}")]
+    MissingResponse(u64),
+}
+
+/// A JSON-RPC batch of responses, serialized as an array, which nodes may
+/// return in any order
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(transparent)]
+#[serde(bound(deserialize = "T: DeserializeOwned"))]
+pub struct BatchResponse<T = Box<RawValue>>(pub Vec<Response<T>>);
+
+impl<T: Serialize> PartialEq for BatchResponse<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl<T> Default for BatchResponse<T> {
+    fn default() -> Self {
+        Self(vec![])
+    }
+}
+
+impl<T> BatchResponse<T> {
+    /// Builds a response for every request of the batch, in the batch order,
+    /// for mocking batch endpoints in tests
+    pub fn mock<P>(batch: &BatchRequest<P>, respond: impl Fn(&Request<P>) -> Response<T>) -> Self {
+        Self(batch.0.iter().map(respond).collect())
+    }
+
+    /// Response to the request with the given id
+    pub fn get(&self, id: u64) -> Option<&Response<T>> {
+        self.0.iter().find(|response| response.id() == id)
+    }
+}
+
+impl<T: Clone> BatchResponse<T> {
+    /// Results of the batch requests matched by id, in the order of the
+    /// requests
+    pub fn results<P>(&self, batch: &BatchRequest<P>) -> Vec<Result<T, BatchItemError>> {
+        batch
+            .ids()
+            .into_iter()
+            .map(|id| match self.get(id) {
+                Some(Response::Success { result, .. }) => Ok(result.clone()),
+                Some(Response::Error { error, .. }) => Err(BatchItemError::RpcError {
+                    id,
+                    error: error.clone(),
+                }),
+                None => Err(BatchItemError::MissingResponse(id)),
+            })
+            .collect()
+    }
+}
+
+impl<T: Serialize> BatchResponse<T> {
     pub fn to_json_string(&self) -> Result<String, serde_json::Error> {
         serde_json::to_string(&self)
     }
@@ -100,11 +516,144 @@ pub struct Error {
     pub data: Option<Value>,
 }
 
+impl Error {
+    /// Classifies this error into a well known node error, if it is one
+    pub fn kind(&self) -> Option<NodeErrorKind> {
+        NodeErrorKind::classify(self.code, &self.message)
+    }
+}
+
+/// Well known JSON-RPC node errors, classified from the error code and the
+/// message wording of geth, erigon, nethermind, anvil and common providers
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NodeErrorKind {
+    /// The call or transaction reverted
+    ExecutionReverted,
+    /// The transaction nonce was already used by the sender
+    NonceTooLow,
+    /// A pending transaction with the same nonce pays an equal or higher fee
+    ReplacementUnderpriced,
+    /// The sender cannot pay for the gas and value
+    InsufficientFunds,
+    /// The gas limit is below the intrinsic cost of the transaction
+    IntrinsicGasTooLow,
+    /// The node or provider throttled the request
+    RateLimited,
+    /// The method is unknown or not enabled on the node
+    MethodNotFound,
+    /// The requested block is unknown, not synced yet or pruned
+    HeaderNotFound,
+}
+
+impl NodeErrorKind {
+    /// Classifies a JSON-RPC error from its code, falling back to its message
+    pub fn classify(code: i64, message: &str) -> Option<Self> {
+        match code {
+            3 => Some(Self::ExecutionReverted),
+            -32601 => Some(Self::MethodNotFound),
+            429 | -32005 => Some(Self::RateLimited),
+            _ => Self::from_message(message),
+        }
+    }
+
+    /// Classifies a JSON-RPC error message, only called for node error
+    /// responses as revert reasons and other messages are free form
+    fn from_message(message: &str) -> Option<Self> {
+        let message = message.to_lowercase();
+        let contains_any = |patterns: &[&str]| patterns.iter().any(|p| message.contains(p));
+
+        // revert reasons are free form, so they are matched before anything
+        // they could be mistaken for
+        if contains_any(&["revert", "vm execution error"]) {
+            Some(Self::ExecutionReverted)
+        } else if contains_any(&[
+            "replacement transaction underpriced",
+            "replacement fee too low",
+            "could not replace existing tx",
+            "replacementnotallowed",
+        ]) {
+            Some(Self::ReplacementUnderpriced)
+        } else if contains_any(&[
+            "nonce too low",
+            "nonce is too low",
+            "oldnonce",
+            "nonce has already been used",
+        ]) {
+            Some(Self::NonceTooLow)
+        } else if contains_any(&[
+            "insufficient funds",
+            "insufficientfunds",
+            "insufficient balance",
+        ]) {
+            Some(Self::InsufficientFunds)
+        } else if contains_any(&["intrinsic gas too low", "intrinsicgastoolow"]) {
+            Some(Self::IntrinsicGasTooLow)
+        } else if contains_any(&[
+            "rate limit",
+            "too many requests",
+            "limit exceeded",
+            "exceeded its compute units",
+        ]) {
+            Some(Self::RateLimited)
+        } else if contains_any(&[
+            "method not found",
+            "does not exist/is not available",
+            "method not supported",
+            "unsupported method",
+        ]) {
+            Some(Self::MethodNotFound)
+        } else if contains_any(&["header not found", "unknown block", "block not found"]) {
+            Some(Self::HeaderNotFound)
+        } else {
+            None
+        }
+    }
+
+    /// Classifies a provider or middleware error from its JSON-RPC error
+    /// response, errors without one did not come from the node
+    pub fn from_middleware_error<E: MiddlewareError>(err: &E) -> Option<Self> {
+        err.as_error_response()
+            .and_then(|err| Self::classify(err.code, &err.message))
+    }
+}
+
+/// A failed node request, its message along with the well known node error
+/// classified from its JSON-RPC error response
+#[derive(thiserror::Error, Debug, Clone, PartialEq, Eq)]
+#[error("{message}")]
+pub struct NodeError {
+    pub message: String,
+    pub kind: Option<NodeErrorKind>,
+}
+
+impl NodeError {
+    pub fn from_middleware_error<E: MiddlewareError>(err: &E) -> Self {
+        Self {
+            message: err.to_string(),
+            kind: NodeErrorKind::from_middleware_error(err),
+        }
+    }
+}
+
+/// A failure that is not a node error response, e.g. a missing block
+impl From<String> for NodeError {
+    fn from(message: String) -> Self {
+        Self {
+            message,
+            kind: None,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::overrides::AccountOverride;
     use crate::request_shim::{AlloyTransactionRequest, TransactionRequestShim};
-    use alloy::primitives::Address;
+    use alloy::rpc::types::RpcBlockHash;
+    use ethers::providers::{HttpClientError, JsonRpcError, ProviderError};
+    use httpmock::{Method::POST, MockServer};
+    use serde_json::json;
 
     #[test]
     fn test_response_to_json() {
@@ -127,17 +676,520 @@ mod tests {
             .with_to(Some(address))
             .with_data(Some(data));
         let transaction = eip2718::TypedTransaction::Eip1559(transaction.to_eip1559());
-        let result = Request::<(eip2718::TypedTransaction, BlockNumber)>::eth_call_request(
-            1,
-            transaction,
+        let result =
+            Request::<(eip2718::TypedTransaction, BlockId)>::eth_call_request(1, transaction, None)
+                .to_json_string()
+                .unwrap();
+        let expected = format!(
+            r#"{{"id":1,"jsonrpc":"2.0","method":"eth_call","params":[{{"to":"{}","data":"0x1234","accessList":[]}},"latest"]}}"#,
+            address.to_string().to_ascii_lowercase()
+        );
+        assert_eq!(result, expected);
+    }
+
+    fn block_number_batch() -> BatchRequest {
+        let mut batch = BatchRequest::new();
+        batch
+            .push(Request::new(1, "eth_blockNumber", json!([])))
+            .push(Request::new(2, "eth_chainId", json!([])))
+            .push(Request::new(3, "eth_gasPrice", json!([])));
+        batch
+    }
+
+    #[test]
+    fn test_batch_request_to_json() {
+        let result = block_number_batch().to_json_string().unwrap();
+        let expected = concat!(
+            r#"[{"id":1,"jsonrpc":"2.0","method":"eth_blockNumber","params":[]},"#,
+            r#"{"id":2,"jsonrpc":"2.0","method":"eth_chainId","params":[]},"#,
+            r#"{"id":3,"jsonrpc":"2.0","method":"eth_gasPrice","params":[]}]"#
+        );
+        assert_eq!(result, expected);
+        assert_eq!(
+            serde_json::from_str::<BatchRequest>(expected).unwrap(),
+            block_number_batch()
+        );
+    }
+
+    #[test]
+    fn test_batch_response_results() {
+        let batch = block_number_batch();
+        // out of order, with an error and a missing item
+        let response: BatchResponse<String> = serde_json::from_str(concat!(
+            r#"[{"jsonrpc":"2.0","id":3,"error":{"code":-32601,"message":"method not found","data":null}},"#,
+            r#"{"jsonrpc":"2.0","id":1,"result":"0x10"}]"#
+        ))
+        .unwrap();
+
+        assert_eq!(
+            response.get(1),
+            Some(&Response::new_typed_success(1, "0x10".to_string()))
+        );
+        assert_eq!(
+            response.results(&batch),
+            vec![
+                Ok("0x10".to_string()),
+                Err(BatchItemError::MissingResponse(2)),
+                Err(BatchItemError::RpcError {
+                    id: 3,
+                    error: Error {
+                        code: -32601,
+                        message: "method not found".to_string(),
+                        data: None,
+                    }
+                }),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_batch_http() -> anyhow::Result<()> {
+        let rpc_server = MockServer::start();
+        let batch = block_number_batch();
+        let mut response = BatchResponse::mock(&batch, |request| {
+            Response::new_block_number(request.id, request.id)
+        });
+        response.0.reverse();
+
+        rpc_server.mock(|when, then| {
+            when.method(POST)
+                .path("/rpc")
+                .json_body_obj(&block_number_batch());
+            then.json_body_obj(&response);
+        });
+
+        let response: BatchResponse<String> = reqwest::Client::new()
+            .post(rpc_server.url("/rpc"))
+            .json(&batch)
+            .send()
+            .await?
+            .json()
+            .await?;
+        assert_eq!(
+            response.results(&batch),
+            vec![
+                Ok("0x1".to_string()),
+                Ok("0x2".to_string()),
+                Ok("0x3".to_string())
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_typed_response() {
+        let json = r#"{"jsonrpc":"2.0","id":1,"result":{"oldestBlock":"0x1","baseFeePerGas":["0x7","0x8"],"gasUsedRatio":[0.5],"reward":[]}}"#;
+
+        // raw by default
+        let response: Response = serde_json::from_str(json).unwrap();
+        let Response::Success { ref result, .. } = response else {
+            panic!("expected a success response");
+        };
+        assert!(result.get().starts_with(r#"{"oldestBlock""#));
+        assert_eq!(response.to_json_string().unwrap(), json);
+
+        // or typed
+        let response: Response<alloy::rpc::types::FeeHistory> = serde_json::from_str(json).unwrap();
+        let Response::Success { result, .. } = response else {
+            panic!("expected a success response");
+        };
+        assert_eq!(result.oldest_block, 1);
+        assert_eq!(result.base_fee_per_gas, vec![7, 8]);
+        assert_eq!(
+            Response::new_fee_history(1, &result)
+                .unwrap()
+                .to_json_string()
+                .unwrap(),
+            Response::new_typed_success(1, &result)
+                .to_json_string()
+                .unwrap()
+        );
+
+        let response: Response<String> =
+            serde_json::from_str(&Response::new_success(2, "0x1234").to_json_string().unwrap())
+                .unwrap();
+        assert_eq!(
+            response,
+            Response::new_typed_success(2, "0x1234".to_string())
+        );
+
+        let response: Response<String> = serde_json::from_str(
+            &Response::new_error(3, -32000, "header not found", None)
+                .to_json_string()
+                .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            response,
+            Response::new_typed_error(3, -32000, "header not found", None)
+        );
+    }
+
+    #[test]
+    fn test_typed_response_constructors() {
+        let response = Response::new_block(1, None).unwrap();
+        assert_eq!(
+            response.to_json_string().unwrap(),
+            r#"{"jsonrpc":"2.0","id":1,"result":null}"#
+        );
+        let response: Response<Option<alloy::rpc::types::Block>> =
+            serde_json::from_str(&response.to_json_string().unwrap()).unwrap();
+        assert_eq!(response, Response::new_typed_success(1, None));
+
+        let response = Response::new_logs(2, &[]).unwrap();
+        assert_eq!(
+            response.to_json_string().unwrap(),
+            r#"{"jsonrpc":"2.0","id":2,"result":[]}"#
+        );
+
+        assert_eq!(
+            Response::new_block_number(3, 255).to_json_string().unwrap(),
+            r#"{"jsonrpc":"2.0","id":3,"result":"0xff"}"#
+        );
+    }
+
+    #[test]
+    fn test_raw_response_eq() {
+        assert_eq!(
+            Response::new_success(1, "0x1234"),
+            Response::new_success(1, "0x1234")
+        );
+        assert_ne!(
+            Response::new_success(1, "0x1234"),
+            Response::new_success(1, "0x5678")
+        );
+        assert_ne!(
+            Response::new_success(1, "0x1234"),
+            Response::new_success(2, "0x1234")
+        );
+        assert_ne!(
+            Response::new_success(1, "0x1234"),
+            Response::new_error(1, -32000, "header not found", None)
+        );
+    }
+
+    fn call_transaction(address: Address) -> eip2718::TypedTransaction {
+        let transaction = AlloyTransactionRequest::new()
+            .with_to(Some(address))
+            .with_data(Some(vec![0x12, 0x34]));
+        eip2718::TypedTransaction::Eip1559(transaction.to_eip1559())
+    }
+
+    #[test]
+    fn test_estimate_gas_request_to_json() {
+        let address = Address::repeat_byte(0x11);
+        let result = Request::eth_estimate_gas_request(1, call_transaction(address), None)
+            .to_json_string()
+            .unwrap();
+        let expected = format!(
+            r#"{{"id":1,"jsonrpc":"2.0","method":"eth_estimateGas","params":[{{"to":"{}","data":"0x1234","accessList":[]}},"latest"]}}"#,
+            address.to_string().to_ascii_lowercase()
+        );
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_create_access_list_request_to_json() {
+        let address = Address::repeat_byte(0x11);
+        let result = Request::eth_create_access_list_request(
+            2,
+            call_transaction(address),
+            Some(BlockId::Number(BlockNumberOrTag::Pending)),
+        )
+        .to_json_string()
+        .unwrap();
+        let expected = format!(
+            r#"{{"id":2,"jsonrpc":"2.0","method":"eth_createAccessList","params":[{{"to":"{}","data":"0x1234","accessList":[]}},"pending"]}}"#,
+            address.to_string().to_ascii_lowercase()
+        );
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_get_logs_request_to_json() {
+        let filter = ethers::types::Filter::new()
+            .address(ethers::types::H160::repeat_byte(0x11))
+            .from_block(100)
+            .to_block(BlockNumber::Latest);
+        let result = Request::eth_get_logs_request(3, filter.clone())
+            .to_json_string()
+            .unwrap();
+        let expected = format!(
+            r#"{{"id":3,"jsonrpc":"2.0","method":"eth_getLogs","params":[{}]}}"#,
+            serde_json::to_string(&filter).unwrap()
+        );
+        assert_eq!(result, expected);
+        assert!(expected.contains(r#""fromBlock":"0x64","toBlock":"latest""#));
+    }
+
+    #[test]
+    fn test_account_requests_to_json() {
+        let address = Address::repeat_byte(0x11);
+        let expected = |method: &str, block: &str| {
+            format!(
+                r#"{{"id":4,"jsonrpc":"2.0","method":"{}","params":["0x1111111111111111111111111111111111111111",{}]}}"#,
+                method, block
+            )
+        };
+
+        let result = Request::eth_get_balance_request(4, address, None)
+            .to_json_string()
+            .unwrap();
+        assert_eq!(result, expected("eth_getBalance", r#""latest""#));
+
+        let result = Request::eth_get_code_request(
+            4,
+            address,
+            Some(BlockId::Number(BlockNumberOrTag::Safe)),
+        )
+        .to_json_string()
+        .unwrap();
+        assert_eq!(result, expected("eth_getCode", r#""safe""#));
+
+        let result =
+            Request::eth_get_transaction_count_request(4, address, Some(BlockId::from(16)))
+                .to_json_string()
+                .unwrap();
+        assert_eq!(result, expected("eth_getTransactionCount", r#""0x10""#));
+
+        let result = Request::eth_get_storage_at_request(4, address, U256::from(2), None)
+            .to_json_string()
+            .unwrap();
+        assert_eq!(
+            result,
+            r#"{"id":4,"jsonrpc":"2.0","method":"eth_getStorageAt","params":["0x1111111111111111111111111111111111111111","0x2","latest"]}"#
+        );
+
+        // EIP-1898 block hash selector
+        let block = BlockId::Hash(RpcBlockHash {
+            block_hash: B256::repeat_byte(0x44),
+            require_canonical: Some(true),
+        });
+        let result = Request::eth_get_balance_request(4, address, Some(block))
+            .to_json_string()
+            .unwrap();
+        assert_eq!(
+            result,
+            expected(
+                "eth_getBalance",
+                r#"{"blockHash":"0x4444444444444444444444444444444444444444444444444444444444444444","requireCanonical":true}"#
+            )
+        );
+    }
+
+    #[test]
+    fn test_fee_history_request_to_json() {
+        let result = Request::eth_fee_history_request(5, 10, None, vec![25.0, 75.0])
+            .to_json_string()
+            .unwrap();
+        assert_eq!(
+            result,
+            r#"{"id":5,"jsonrpc":"2.0","method":"eth_feeHistory","params":["0xa","latest",[25.0,75.0]]}"#
+        );
+    }
+
+    #[test]
+    fn test_get_block_requests_to_json() {
+        let result =
+            Request::eth_get_block_by_number_request(6, BlockNumberOrTag::Finalized, false)
+                .to_json_string()
+                .unwrap();
+        assert_eq!(
+            result,
+            r#"{"id":6,"jsonrpc":"2.0","method":"eth_getBlockByNumber","params":["finalized",false]}"#
+        );
+
+        let result = Request::eth_get_block_by_hash_request(7, B256::repeat_byte(0x22), true)
+            .to_json_string()
+            .unwrap();
+        assert_eq!(
+            result,
+            r#"{"id":7,"jsonrpc":"2.0","method":"eth_getBlockByHash","params":["0x2222222222222222222222222222222222222222222222222222222222222222",true]}"#
+        );
+    }
+
+    #[test]
+    fn test_call_with_state_override_request_to_json() {
+        let address = Address::repeat_byte(0x11);
+        let state_override = StateOverride::from([(
+            Address::repeat_byte(0x22),
+            AccountOverride {
+                balance: Some(U256::from(100)),
+                ..Default::default()
+            },
+        )]);
+        let result = Request::eth_call_with_state_override_request(
+            8,
+            call_transaction(address),
             None,
+            state_override,
         )
         .to_json_string()
         .unwrap();
         let expected = format!(
-            r#"{{"id":1,"jsonrpc":"2.0","method":"eth_call","params":[{{"to":"{}","data":"0x1234","accessList":[]}},"latest"]}}"#,
+            r#"{{"id":8,"jsonrpc":"2.0","method":"eth_call","params":[{{"to":"{}","data":"0x1234","accessList":[]}},"latest",{{"0x2222222222222222222222222222222222222222":{{"balance":"0x64"}}}}]}}"#,
             address.to_string().to_ascii_lowercase()
         );
         assert_eq!(result, expected);
     }
+
+    #[test]
+    fn test_call_with_overrides_request_to_json() {
+        let address = Address::repeat_byte(0x11);
+        let block_overrides = BlockOverrides {
+            time: Some(U64::from(1_700_000_000)),
+            ..Default::default()
+        };
+        let result = Request::eth_call_with_overrides_request(
+            9,
+            call_transaction(address),
+            Some(BlockId::Number(BlockNumberOrTag::Pending)),
+            None,
+            block_overrides,
+        )
+        .to_json_string()
+        .unwrap();
+        let expected = format!(
+            r#"{{"id":9,"jsonrpc":"2.0","method":"eth_call","params":[{{"to":"{}","data":"0x1234","accessList":[]}},"pending",{{}},{{"time":"0x6553f100"}}]}}"#,
+            address.to_string().to_ascii_lowercase()
+        );
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_call_request_at_block_hash_to_json() {
+        let address = Address::repeat_byte(0x11);
+        let block = BlockId::Hash(RpcBlockHash {
+            block_hash: B256::repeat_byte(0x22),
+            require_canonical: Some(true),
+        });
+        let result = Request::<(eip2718::TypedTransaction, BlockId)>::eth_call_request(
+            10,
+            call_transaction(address),
+            Some(block),
+        )
+        .to_json_string()
+        .unwrap();
+        let expected = format!(
+            r#"{{"id":10,"jsonrpc":"2.0","method":"eth_call","params":[{{"to":"{}","data":"0x1234","accessList":[]}},{{"blockHash":"0x2222222222222222222222222222222222222222222222222222222222222222","requireCanonical":true}}]}}"#,
+            address.to_string().to_ascii_lowercase()
+        );
+        assert_eq!(result, expected);
+
+        let result = Request::<(eip2718::TypedTransaction, BlockId)>::eth_call_request(
+            11,
+            call_transaction(address),
+            Some(BlockId::Number(BlockNumberOrTag::Finalized)),
+        )
+        .to_json_string()
+        .unwrap();
+        assert!(result.ends_with(r#""accessList":[]},"finalized"]}"#));
+    }
+
+    #[test]
+    fn test_node_error_kind() {
+        let cases = [
+            // geth, erigon and anvil
+            (
+                3,
+                "execution reverted: not owner",
+                NodeErrorKind::ExecutionReverted,
+            ),
+            (-32000, "nonce too low", NodeErrorKind::NonceTooLow),
+            (
+                -32000,
+                "replacement transaction underpriced",
+                NodeErrorKind::ReplacementUnderpriced,
+            ),
+            (
+                -32000,
+                "insufficient funds for gas * price + value: have 0 want 21000",
+                NodeErrorKind::InsufficientFunds,
+            ),
+            (
+                -32000,
+                "intrinsic gas too low: have 20000, want 21000",
+                NodeErrorKind::IntrinsicGasTooLow,
+            ),
+            (-32000, "header not found", NodeErrorKind::HeaderNotFound),
+            (
+                -32601,
+                "the method eth_foo does not exist/is not available",
+                NodeErrorKind::MethodNotFound,
+            ),
+            // nethermind
+            (
+                -32015,
+                "VM execution error.",
+                NodeErrorKind::ExecutionReverted,
+            ),
+            (-32010, "OldNonce", NodeErrorKind::NonceTooLow),
+            (
+                -32010,
+                "InsufficientFunds",
+                NodeErrorKind::InsufficientFunds,
+            ),
+            (
+                -32010,
+                "IntrinsicGasTooLow",
+                NodeErrorKind::IntrinsicGasTooLow,
+            ),
+            (-32001, "Block not found", NodeErrorKind::HeaderNotFound),
+            // providers
+            (
+                -32005,
+                "daily request count exceeded",
+                NodeErrorKind::RateLimited,
+            ),
+            (
+                429,
+                "Your app has exceeded its compute units per second capacity",
+                NodeErrorKind::RateLimited,
+            ),
+            (-32000, "rate limit reached", NodeErrorKind::RateLimited),
+        ];
+        for (code, message, expected) in cases {
+            let error = Error {
+                code,
+                message: message.to_string(),
+                data: None,
+            };
+            assert_eq!(error.kind(), Some(expected), "{message}");
+        }
+
+        let error = Error {
+            code: -32602,
+            message: "invalid argument 0: hex string without 0x prefix".to_string(),
+            data: None,
+        };
+        assert_eq!(error.kind(), None);
+
+        // custom revert reasons are not taken for the error they mention
+        assert_eq!(
+            NodeErrorKind::from_message("execution reverted: nonce too low"),
+            Some(NodeErrorKind::ExecutionReverted)
+        );
+    }
+
+    #[test]
+    fn test_node_error_from_middleware_error() {
+        let err = ProviderError::from(HttpClientError::JsonRpcError(JsonRpcError {
+            code: -32000,
+            message: "nonce too low".to_string(),
+            data: None,
+        }));
+        let node_error = NodeError::from_middleware_error(&err);
+        assert_eq!(node_error.kind, Some(NodeErrorKind::NonceTooLow));
+        assert_eq!(node_error.message, err.to_string());
+
+        // only node error responses are classified, not any error message
+        let err = ProviderError::CustomError("gas limit exceeded, tx reverted".to_string());
+        assert_eq!(NodeError::from_middleware_error(&err).kind, None);
+
+        assert_eq!(
+            NodeError::from("latest block not found".to_string()).kind,
+            None
+        );
+    }
 }
@@ -0,0 +1,295 @@
+//! Serde adapters for structs mixing ethers and alloy types, to be used with
+//! `#[serde(with = "...")]`.
+//!
+//! Modules named `ethers_*` (de)serialize an alloy field in exactly the wire
+//! format of its ethers counterpart, modules named `alloy_*` do the reverse
+//! for an ethers field, so persisted data does not change when a struct
+//! migrates from one library to the other.
+//!
+//! ```
+//! use alloy::primitives::{Address, U256};
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct Config {
+//!     #[serde(with = "alloy_ethers_typecast::serde_compat::ethers_address")]
+//!     orderbook: Address,
+//!     #[serde(with = "alloy_ethers_typecast::serde_compat::ethers_u256")]
+//!     amount: U256,
+//! }
+//! ```
+
+use crate::convert::{ToAlloy, ToEthers, TryToAlloy};
+
+macro_rules! impl_serde_compat {
+    ($ethers_mod:ident, $alloy_mod:ident, $ethers:ty, $alloy:ty) => {
+        #[doc = concat!(
+            "(De)serializes [`", stringify!($alloy), "`] in the wire format of [`",
+            stringify!($ethers), "`]"
+        )]
+        pub mod $ethers_mod {
+            use super::*;
+            use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+            pub fn serialize<S: Serializer>(
+                value: &$alloy,
+                serializer: S,
+            ) -> Result<S::Ok, S::Error> {
+                value.to_owned().to_ethers().serialize(serializer)
+            }
+
+            pub fn deserialize<'de, D: Deserializer<'de>>(
+                deserializer: D,
+            ) -> Result<$alloy, D::Error> {
+                <$ethers>::deserialize(deserializer).map(ToAlloy::to_alloy)
+            }
+        }
+
+        #[doc = concat!(
+            "(De)serializes [`", stringify!($ethers), "`] in the wire format of [`",
+            stringify!($alloy), "`]"
+        )]
+        pub mod $alloy_mod {
+            use super::*;
+            use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+            pub fn serialize<S: Serializer>(
+                value: &$ethers,
+                serializer: S,
+            ) -> Result<S::Ok, S::Error> {
+                value.to_owned().to_alloy().serialize(serializer)
+            }
+
+            pub fn deserialize<'de, D: Deserializer<'de>>(
+                deserializer: D,
+            ) -> Result<$ethers, D::Error> {
+                <$alloy>::deserialize(deserializer).map(ToEthers::to_ethers)
+            }
+        }
+    };
+}
+
+impl_serde_compat!(
+    ethers_address,
+    alloy_address,
+    ethers::types::Address,
+    alloy::primitives::Address
+);
+impl_serde_compat!(
+    ethers_u256,
+    alloy_u256,
+    ethers::types::U256,
+    alloy::primitives::U256
+);
+impl_serde_compat!(
+    ethers_u64,
+    alloy_u64,
+    ethers::types::U64,
+    alloy::primitives::U64
+);
+impl_serde_compat!(
+    ethers_bytes,
+    alloy_bytes,
+    ethers::types::Bytes,
+    alloy::primitives::Bytes
+);
+impl_serde_compat!(
+    ethers_h256,
+    alloy_b256,
+    ethers::types::H256,
+    alloy::primitives::B256
+);
+
+/// (De)serializes [`alloy::rpc::types::Log`] in the wire format of
+/// [`ethers::types::Log`], ethers only `transactionLogIndex` and `logType`
+/// are dropped on deserialization
+pub mod ethers_log {
+    use super::*;
+    use serde::{de::Error, Deserialize, Deserializer, Serialize, Serializer};
+
+    pub fn serialize<S: Serializer>(
+        value: &alloy::rpc::types::Log,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        value.clone().to_ethers().serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<alloy::rpc::types::Log, D::Error> {
+        ethers::types::Log::deserialize(deserializer)?
+            .try_to_alloy()
+            .map_err(D::Error::custom)
+    }
+}
+
+/// (De)serializes [`ethers::types::Log`] in the wire format of
+/// [`alloy::rpc::types::Log`], ethers only `transactionLogIndex` and `logType`
+/// are dropped on serialization
+pub mod alloy_log {
+    use super::*;
+    use serde::{ser::Error, Deserialize, Deserializer, Serialize, Serializer};
+
+    pub fn serialize<S: Serializer>(
+        value: &ethers::types::Log,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        let log: alloy::rpc::types::Log = value.clone().try_to_alloy().map_err(S::Error::custom)?;
+        log.serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<ethers::types::Log, D::Error> {
+        alloy::rpc::types::Log::deserialize(deserializer).map(ToEthers::to_ethers)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::primitives::{Address, Bytes, B256, U256, U64};
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct AlloyFields {
+        #[serde(with = "ethers_address")]
+        address: Address,
+        #[serde(with = "ethers_u256")]
+        amount: U256,
+        #[serde(with = "ethers_u64")]
+        block: U64,
+        #[serde(with = "ethers_bytes")]
+        data: Bytes,
+        #[serde(with = "ethers_h256")]
+        hash: B256,
+    }
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct EthersFields {
+        address: ethers::types::Address,
+        amount: ethers::types::U256,
+        block: ethers::types::U64,
+        data: ethers::types::Bytes,
+        hash: ethers::types::H256,
+    }
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct EthersFieldsAlloyWire {
+        #[serde(with = "alloy_address")]
+        address: ethers::types::Address,
+        #[serde(with = "alloy_u256")]
+        amount: ethers::types::U256,
+        #[serde(with = "alloy_u64")]
+        block: ethers::types::U64,
+        #[serde(with = "alloy_bytes")]
+        data: ethers::types::Bytes,
+        #[serde(with = "alloy_b256")]
+        hash: ethers::types::H256,
+    }
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct AlloyLog {
+        #[serde(with = "ethers_log")]
+        log: alloy::rpc::types::Log,
+    }
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct EthersLog {
+        #[serde(with = "alloy_log")]
+        log: ethers::types::Log,
+    }
+
+    const SNAPSHOT: &str = concat!(
+        r#"{"address":"0x1111111111111111111111111111111111111111","#,
+        r#""amount":"0x3e8","block":"0x64","data":"0xdeadbeef","#,
+        r#""hash":"0x2222222222222222222222222222222222222222222222222222222222222222"}"#
+    );
+
+    fn ethers_fields() -> EthersFields {
+        EthersFields {
+            address: ethers::types::Address::repeat_byte(0x11),
+            amount: ethers::types::U256::from(1000),
+            block: ethers::types::U64::from(100),
+            data: ethers::types::Bytes::from(vec![0xde, 0xad, 0xbe, 0xef]),
+            hash: ethers::types::H256::repeat_byte(0x22),
+        }
+    }
+
+    fn sample_log() -> ethers::types::Log {
+        ethers::types::Log {
+            address: ethers::types::Address::repeat_byte(0x11),
+            topics: vec![ethers::types::H256::repeat_byte(0x22)],
+            data: ethers::types::Bytes::from(vec![0xde, 0xad, 0xbe, 0xef]),
+            block_hash: Some(ethers::types::H256::repeat_byte(0x33)),
+            block_number: Some(ethers::types::U64::from(100)),
+            transaction_hash: Some(ethers::types::H256::repeat_byte(0x44)),
+            transaction_index: Some(ethers::types::U64::from(2)),
+            log_index: Some(ethers::types::U256::from(7)),
+            removed: Some(false),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_alloy_fields_in_ethers_format() {
+        let ethers_json = serde_json::to_string(&ethers_fields()).unwrap();
+        assert_eq!(ethers_json, SNAPSHOT);
+
+        let alloy_fields = AlloyFields {
+            address: Address::repeat_byte(0x11),
+            amount: U256::from(1000),
+            block: U64::from(100),
+            data: Bytes::from(vec![0xde, 0xad, 0xbe, 0xef]),
+            hash: B256::repeat_byte(0x22),
+        };
+        assert_eq!(serde_json::to_string(&alloy_fields).unwrap(), SNAPSHOT);
+        assert_eq!(
+            serde_json::from_str::<AlloyFields>(SNAPSHOT).unwrap(),
+            alloy_fields
+        );
+    }
+
+    #[test]
+    fn test_ethers_fields_in_alloy_format() {
+        let fields = EthersFieldsAlloyWire {
+            address: ethers::types::Address::repeat_byte(0x11),
+            amount: ethers::types::U256::from(1000),
+            block: ethers::types::U64::from(100),
+            data: ethers::types::Bytes::from(vec![0xde, 0xad, 0xbe, 0xef]),
+            hash: ethers::types::H256::repeat_byte(0x22),
+        };
+        assert_eq!(serde_json::to_string(&fields).unwrap(), SNAPSHOT);
+        assert_eq!(
+            serde_json::from_str::<EthersFieldsAlloyWire>(SNAPSHOT).unwrap(),
+            fields
+        );
+    }
+
+    #[test]
+    fn test_log_in_ethers_format() {
+        let log = sample_log();
+        let expected = format!(r#"{{"log":{}}}"#, serde_json::to_string(&log).unwrap());
+
+        let alloy_log = AlloyLog {
+            log: log.try_to_alloy().unwrap(),
+        };
+        let json = serde_json::to_string(&alloy_log).unwrap();
+        assert_eq!(json, expected);
+        assert_eq!(serde_json::from_str::<AlloyLog>(&json).unwrap(), alloy_log);
+    }
+
+    #[test]
+    fn test_log_in_alloy_format() {
+        let log: alloy::rpc::types::Log = sample_log().try_to_alloy().unwrap();
+        let expected = format!(r#"{{"log":{}}}"#, serde_json::to_string(&log).unwrap());
+
+        let ethers_log = EthersLog { log: sample_log() };
+        let json = serde_json::to_string(&ethers_log).unwrap();
+        assert_eq!(json, expected);
+        assert_eq!(
+            serde_json::from_str::<EthersLog>(&json).unwrap(),
+            ethers_log
+        );
+    }
+}
@@ -0,0 +1,144 @@
+use crate::convert::{ConversionError, ToAlloy, ToEthers, TryToAlloy};
+use alloy::primitives::{Address, Signature, B256};
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum SignatureRecoveryError {
+    #[error(transparent)]
+    ConversionError(#[from] ConversionError),
+    #[error(transparent)]
+    EthersSignatureError(#[from] ethers::types::SignatureError),
+    #[error(transparent)]
+    AlloySignatureError(#[from] alloy::primitives::SignatureError),
+    #[error("recovered signers differ, ethers: {ethers}, alloy: {alloy}")]
+    SignerMismatch { ethers: Address, alloy: Address },
+}
+
+/// ethers keeps `v` as a plain number, which is normalised into the matching
+/// alloy parity form: `0`/`1` y-parity of typed transactions, `27`/`28` legacy
+/// and `35` onwards EIP-155 with the chain id encoded in it
+impl TryToAlloy<Signature> for ethers::types::Signature {
+    fn try_to_alloy(self) -> Result<Signature, ConversionError> {
+        Signature::from_rs_and_parity(self.r.to_alloy(), self.s.to_alloy(), self.v)
+            .map_err(|err| ConversionError::InvalidSignature(err.to_string()))
+    }
+}
+
+/// `v` is kept in the same form as the alloy parity
+impl ToEthers for Signature {
+    type To = ethers::types::Signature;
+
+    fn to_ethers(self) -> Self::To {
+        ethers::types::Signature {
+            r: self.r().to_ethers(),
+            s: self.s().to_ethers(),
+            v: self.v().to_u64(),
+        }
+    }
+}
+
+/// Recovers the signer of an EIP-191 personal message through both ethers and
+/// alloy, erroring if they do not agree
+pub fn recover_address_from_msg(
+    signature: ethers::types::Signature,
+    message: impl AsRef<[u8]>,
+) -> Result<Address, SignatureRecoveryError> {
+    let ethers_signer = signature.recover(message.as_ref().to_vec())?.to_alloy();
+    let alloy_signer = TryToAlloy::<Signature>::try_to_alloy(signature)?
+        .recover_address_from_msg(message.as_ref())?;
+    check_signers(ethers_signer, alloy_signer)
+}
+
+/// Recovers the signer of a prehashed message through both ethers and alloy,
+/// erroring if they do not agree
+pub fn recover_address_from_prehash(
+    signature: ethers::types::Signature,
+    prehash: B256,
+) -> Result<Address, SignatureRecoveryError> {
+    let ethers_signer = signature.recover(prehash.to_ethers())?.to_alloy();
+    let alloy_signer =
+        TryToAlloy::<Signature>::try_to_alloy(signature)?.recover_address_from_prehash(&prehash)?;
+    check_signers(ethers_signer, alloy_signer)
+}
+
+fn check_signers(ethers: Address, alloy: Address) -> Result<Address, SignatureRecoveryError> {
+    if ethers != alloy {
+        return Err(SignatureRecoveryError::SignerMismatch { ethers, alloy });
+    }
+    Ok(alloy)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::primitives::{keccak256, Parity, U256};
+    use ethers::core::rand::thread_rng;
+    use ethers::signers::{LocalWallet, Signer};
+
+    #[test]
+    fn test_v_normalisation() {
+        let r = ethers::types::U256::from(1);
+        let s = ethers::types::U256::from(2);
+        let cases = [
+            (0, Parity::Parity(false), false),
+            (1, Parity::Parity(true), true),
+            (27, Parity::NonEip155(false), false),
+            (28, Parity::NonEip155(true), true),
+            (37, Parity::Eip155(37), false),
+            (38, Parity::Eip155(38), true),
+        ];
+        for (v, parity, y_parity) in cases {
+            let signature = ethers::types::Signature { r, s, v };
+            let alloy_signature: Signature = signature.try_to_alloy().unwrap();
+            assert_eq!(alloy_signature.v(), parity);
+            assert_eq!(alloy_signature.v().y_parity(), y_parity);
+            assert_eq!(alloy_signature.r(), U256::from(1));
+            assert_eq!(alloy_signature.s(), U256::from(2));
+            assert_eq!(alloy_signature.to_ethers(), signature);
+        }
+        assert_eq!(Parity::Eip155(37).chain_id(), Some(1));
+    }
+
+    #[test]
+    fn test_invalid_v() {
+        let signature = ethers::types::Signature {
+            r: ethers::types::U256::from(1),
+            s: ethers::types::U256::from(2),
+            v: 5,
+        };
+        let result: Result<Signature, _> = signature.try_to_alloy();
+        assert!(matches!(result, Err(ConversionError::InvalidSignature(_))));
+    }
+
+    #[tokio::test]
+    async fn test_recover_address_from_msg() -> anyhow::Result<()> {
+        let wallet = LocalWallet::new(&mut thread_rng());
+        let message = "hello rain";
+        let signature = wallet.sign_message(message).await?;
+
+        let signer = recover_address_from_msg(signature, message)?;
+        assert_eq!(signer, wallet.address().to_alloy());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_recover_address_from_prehash() -> anyhow::Result<()> {
+        let wallet = LocalWallet::new(&mut thread_rng());
+        let prehash = keccak256("hello rain");
+        let signature = wallet.sign_hash(prehash.to_ethers())?;
+
+        let signer = recover_address_from_prehash(signature, prehash)?;
+        assert_eq!(signer, wallet.address().to_alloy());
+
+        // EIP-155 form of the same signature recovers the same signer
+        let signature = ethers::types::Signature {
+            v: signature.v - 27 + 35 + 2 * 137,
+            ..signature
+        };
+        let signer = recover_address_from_prehash(signature, prehash)?;
+        assert_eq!(signer, wallet.address().to_alloy());
+
+        Ok(())
+    }
+}
@@ -21,6 +21,7 @@ pub struct MockMiddleware<M> {
     inner: M,
     assert_data: Option<Bytes>,
     assert_to: Option<Address>,
+    assert_access_list: Option<AccessList>,
 }
 
 impl<M> MockMiddleware<M>
@@ -35,6 +36,7 @@ where
             inner,
             assert_data: None,
             assert_to: None,
+            assert_access_list: None,
         })
     }
 
@@ -49,6 +51,12 @@ where
     pub fn assert_next_to(&mut self, to: Address) {
         self.assert_to = Some(to);
     }
+
+    /// Sets the access list that the next transaction should have.
+    #[allow(dead_code)]
+    pub fn assert_next_access_list(&mut self, access_list: AccessList) {
+        self.assert_access_list = Some(access_list);
+    }
 }
 
 #[cfg_attr(not(target_family = "wasm"), async_trait)]
@@ -140,6 +148,11 @@ where
             debug!("Checking to address: {:?} == {:?}", tx.to(), Some(to));
             assert_eq!(tx.to(), Some(&NameOrAddress::Address(*to)));
         }
+
+        // Check the access list, if it's set
+        if let Some(access_list) = &self.assert_access_list {
+            assert_eq!(tx.access_list(), Some(access_list));
+        }
         Ok(
             PendingTransaction::new(H256::from_uint(&U256::from(1)), self.provider())
                 .interval(std::time::Duration::from_secs(0)),
@@ -252,6 +265,12 @@ impl JsonRpcClient for MockJsonRpcClient {
 
                 Ok(response)
             }
+            "eth_chainId" => {
+                debug!("MockJsonRpcClient: called eth_chainId");
+                Ok(serde_json::from_value(json!("0x1")).map_err(|_| {
+                    ProviderError::CustomError("Failed to deserialize response".into())
+                })?)
+            }
             "eth_blockNumber" => {
                 debug!("MockJsonRpcClient: called eth_blockNumber");
                 Ok(serde_json::from_value(json!("0x10")).map_err(|_| {
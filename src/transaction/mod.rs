@@ -6,3 +6,24 @@ pub mod write_transaction;
 pub use read::*;
 pub use write::*;
 pub use write_transaction::*;
+
+use crate::request_shim::TransactionKind;
+use crate::rpc::NodeError;
+use ethers::providers::Middleware;
+
+/// Detects the transaction kind the chain supports from the latest block,
+/// EIP-1559 if it has a base fee and legacy otherwise
+pub(crate) async fn detect_transaction_kind<M: Middleware>(
+    client: &M,
+) -> Result<TransactionKind, NodeError> {
+    let block = client
+        .get_block(ethers::types::BlockNumber::Latest)
+        .await
+        .map_err(|err| NodeError::from_middleware_error(&err))?
+        .ok_or_else(|| NodeError::from("latest block not found".to_string()))?;
+
+    Ok(match block.base_fee_per_gas {
+        Some(_) => TransactionKind::Eip1559,
+        None => TransactionKind::Legacy,
+    })
+}
@@ -1,10 +1,12 @@
-use crate::request_shim::{AlloyTransactionRequest, TransactionRequestShim};
-use crate::{alloy_u64_to_ethers, ethers_u256_to_alloy};
-use alloy::primitives::{Address, U256, U64};
+use crate::ethers_u256_to_alloy;
+use crate::overrides::{BlockOverrides, StateOverride};
+use crate::request_shim::{AlloyTransactionRequest, TransactionKind, TransactionRequestShim};
+use crate::rpc::{NodeError, NodeErrorKind};
+use alloy::primitives::{Address, U256};
+use alloy::rpc::types::{BlockId, BlockNumberOrTag};
 use alloy::sol_types::SolCall;
 use derive_builder::Builder;
-use ethers::providers::{Http, JsonRpcClient, Middleware, Provider, ProviderError};
-use ethers::types::transaction::eip2718::TypedTransaction;
+use ethers::providers::{Http, JsonRpcClient, Middleware, Provider, ProviderError, RpcError};
 use thiserror::Error;
 
 use rain_error_decoding::{AbiDecodeFailedErrors, AbiDecodedErrorType};
@@ -18,23 +20,51 @@ pub enum ReadableClientError {
     #[error("failed to decode return: {0}")]
     ReadDecodeReturnError(String),
     #[error("failed to get chain id: {0}")]
-    ReadChainIdError(String),
+    ReadChainIdError(NodeError),
     #[error("failed to get block number: {0}")]
-    ReadBlockNumberError(String),
+    ReadBlockNumberError(NodeError),
+    #[error("failed to detect transaction kind: {0}")]
+    ReadTransactionKindError(NodeError),
+    #[error("node does not support eth_call block overrides: {0}")]
+    ReadBlockOverridesUnsupported(String),
     #[error(transparent)]
     AbiDecodeFailedErrors(#[from] AbiDecodeFailedErrors),
     #[error(transparent)]
     AbiDecodedErrorType(#[from] AbiDecodedErrorType),
 }
 
+impl ReadableClientError {
+    /// The well known node error behind this error, if any
+    pub fn node_error(&self) -> Option<NodeErrorKind> {
+        match self {
+            ReadableClientError::ReadCallError(err) => NodeErrorKind::from_middleware_error(err),
+            ReadableClientError::ReadChainIdError(err)
+            | ReadableClientError::ReadBlockNumberError(err)
+            | ReadableClientError::ReadTransactionKindError(err) => err.kind,
+            ReadableClientError::AbiDecodedErrorType(_) => Some(NodeErrorKind::ExecutionReverted),
+            _ => None,
+        }
+    }
+}
+
 #[derive(Builder)]
 pub struct ReadContractParameters<C: SolCall> {
     pub address: Address,
     pub call: C,
+    /// Block to read at, a tag, number or EIP-1898 block hash, latest if not set
     #[builder(setter(into), default)]
-    pub block_number: Option<U64>,
+    pub block: Option<BlockId>,
     #[builder(setter(into), default)]
     pub gas: Option<U256>,
+    /// Type of the call transaction, EIP-1559 if not set
+    #[builder(setter(into), default)]
+    pub transaction_kind: Option<TransactionKind>,
+    /// Account state overrides to simulate the call against
+    #[builder(setter(into), default)]
+    pub state_override: Option<StateOverride>,
+    /// Block environment overrides to simulate the call in
+    #[builder(setter(into), default)]
+    pub block_overrides: Option<BlockOverrides>,
 }
 
 #[derive(Clone)]
@@ -68,21 +98,61 @@ impl<P: JsonRpcClient> ReadableClient<P> {
             .with_data(Some(data))
             .with_gas(parameters.gas);
 
-        let res = self
-            .0
-            .call(
-                &TypedTransaction::Eip1559(transaction_request.to_eip1559()),
-                parameters.block_number.map(|val| {
-                    ethers::types::BlockId::Number(ethers::types::BlockNumber::Number(
-                        alloy_u64_to_ethers(val),
-                    ))
-                }),
-            )
-            .await;
+        let transaction =
+            transaction_request.to_typed(parameters.transaction_kind.unwrap_or_default());
+        let block = parameters
+            .block
+            .unwrap_or(BlockId::Number(BlockNumberOrTag::Latest));
+
+        // the raw eth_call is sent as ethers call has neither the override params
+        // nor EIP-1898 requireCanonical block hashes
+        let res = match (parameters.state_override, parameters.block_overrides) {
+            (None, None) => {
+                self.0
+                    .request::<_, ethers::types::Bytes>("eth_call", (&transaction, block))
+                    .await
+            }
+            (Some(state_override), None) => {
+                self.0
+                    .request::<_, ethers::types::Bytes>(
+                        "eth_call",
+                        (&transaction, block, state_override),
+                    )
+                    .await
+            }
+            (state_override, Some(block_overrides)) => {
+                let res = self
+                    .0
+                    .request::<_, ethers::types::Bytes>(
+                        "eth_call",
+                        (
+                            &transaction,
+                            block,
+                            state_override.unwrap_or_default(),
+                            block_overrides,
+                        ),
+                    )
+                    .await;
+                match res {
+                    Err(err) if is_too_many_params_error(&err) => {
+                        return Err(ReadableClientError::ReadBlockOverridesUnsupported(
+                            err.to_string(),
+                        ));
+                    }
+                    res => res,
+                }
+            }
+        };
 
         let res = match res {
             Ok(res) => res,
             Err(err) => {
+                // only reverts carry error data to decode, other node errors are kept as is
+                if NodeErrorKind::from_middleware_error(&err)
+                    .is_some_and(|kind| kind != NodeErrorKind::ExecutionReverted)
+                {
+                    return Err(ReadableClientError::ReadCallError(err));
+                }
                 let err = AbiDecodedErrorType::try_from_provider_error(err).await?;
                 return Err(ReadableClientError::AbiDecodedErrorType(err));
             }
@@ -95,30 +165,56 @@ impl<P: JsonRpcClient> ReadableClient<P> {
     }
 
     pub async fn get_chainid(&self) -> Result<U256, ReadableClientError> {
-        let chainid = self
-            .0
-            .get_chainid()
-            .await
-            .map_err(|err| ReadableClientError::ReadChainIdError(err.to_string()))?;
+        let chainid = self.0.get_chainid().await.map_err(|err| {
+            ReadableClientError::ReadChainIdError(NodeError::from_middleware_error(&err))
+        })?;
 
         Ok(ethers_u256_to_alloy(chainid))
     }
 
     pub async fn get_block_number(&self) -> Result<u64, ReadableClientError> {
-        let block_number = self
-            .0
-            .get_block_number()
-            .await
-            .map_err(|err| ReadableClientError::ReadBlockNumberError(err.to_string()))?;
+        let block_number = self.0.get_block_number().await.map_err(|err| {
+            ReadableClientError::ReadBlockNumberError(NodeError::from_middleware_error(&err))
+        })?;
 
         Ok(block_number.as_u64())
     }
+
+    /// Detects the transaction kind the chain supports from the latest block,
+    /// EIP-1559 if it has a base fee and legacy otherwise
+    pub async fn detect_transaction_kind(&self) -> Result<TransactionKind, ReadableClientError> {
+        super::detect_transaction_kind(&self.0)
+            .await
+            .map_err(ReadableClientError::ReadTransactionKindError)
+    }
+}
+
+/// Wordings of the params count errors of nodes that only take the standard
+/// eth_call params, e.g. geth "too many arguments, want at most 3"
+const TOO_MANY_PARAMS_MESSAGES: [&str; 4] = [
+    "too many arguments",
+    "too many params",
+    "wrong number of arguments",
+    "invalid number of params",
+];
+
+/// Whether the node rejected the extra eth_call params, other invalid params
+/// errors are about the call itself and are not matched
+fn is_too_many_params_error(err: &ProviderError) -> bool {
+    err.as_error_response().is_some_and(|err| {
+        let message = err.message.to_lowercase();
+        TOO_MANY_PARAMS_MESSAGES
+            .iter()
+            .any(|wording| message.contains(wording))
+    })
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use alloy::primitives::{hex::encode, Address, U256};
+    use crate::overrides::{AccountOverride, BlockOverrides};
+    use alloy::primitives::{hex::encode, Address, B256, U256, U64};
+    use alloy::rpc::types::RpcBlockHash;
     use alloy::sol;
     use ethers::providers::{JsonRpcError, MockProvider, MockResponse};
     use serde_json::json;
@@ -134,7 +230,7 @@ mod tests {
 
     #[tokio::test]
     async fn test_builder() -> anyhow::Result<()> {
-        // block_number is optional so this should work
+        // block is optional so this should work
         let parameters = ReadContractParametersBuilder::default()
             .address(Address::repeat_byte(0x11))
             .call(fooCall {
@@ -147,19 +243,25 @@ mod tests {
         assert_eq!(parameters.call.a, U256::from(42));
         assert_eq!(parameters.call.b, U256::from(10));
 
-        // but we can also set block number without needing Some(block_number)
+        // but we can also set the block without needing Some(block)
         let parameters = ReadContractParametersBuilder::default()
             .address(Address::repeat_byte(0x11))
             .call(fooCall {
                 a: U256::from(42),
                 b: U256::from(10),
             })
-            .block_number(Some(U64::from(1)))
+            .block(BlockId::Number(BlockNumberOrTag::Number(1)))
+            .transaction_kind(TransactionKind::Legacy)
             .build()?;
 
         assert_eq!(parameters.address, Address::repeat_byte(0x11));
         assert_eq!(parameters.call.a, U256::from(42));
         assert_eq!(parameters.call.b, U256::from(10));
+        assert_eq!(parameters.transaction_kind, Some(TransactionKind::Legacy));
+        assert_eq!(
+            parameters.block,
+            Some(BlockId::Number(BlockNumberOrTag::Number(1)))
+        );
 
         Ok(())
     }
@@ -206,6 +308,181 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_read_at_block() -> anyhow::Result<()> {
+        let mock_provider = MockProvider::new();
+        let foo_response = MockResponse::Value(json!("0x000000000000000000000000000000000000000000000000000000000000002a0000000000000000000000001111111111111111111111111111111111111111"));
+        mock_provider.push_response(foo_response.clone());
+        mock_provider.push_response(foo_response);
+
+        let read_contract = ReadableClient::new(Provider::new(mock_provider.clone()));
+
+        let blocks = [
+            BlockId::Number(BlockNumberOrTag::Safe),
+            BlockId::Hash(RpcBlockHash {
+                block_hash: B256::repeat_byte(0x44),
+                require_canonical: Some(true),
+            }),
+        ];
+        for block in blocks {
+            let parameters = ReadContractParametersBuilder::default()
+                .call(fooCall {
+                    a: U256::from(42),
+                    b: U256::from(10),
+                })
+                .address(Address::repeat_byte(0x22))
+                .block(block)
+                .build()?;
+            read_contract.read(parameters).await?;
+        }
+
+        let transaction = AlloyTransactionRequest::new()
+            .with_to(Some(Address::repeat_byte(0x22)))
+            .with_data(Some(
+                fooCall {
+                    a: U256::from(42),
+                    b: U256::from(10),
+                }
+                .abi_encode(),
+            ))
+            .to_typed(TransactionKind::Eip1559);
+        mock_provider.assert_request("eth_call", (&transaction, "safe"))?;
+        mock_provider.assert_request(
+            "eth_call",
+            (
+                &transaction,
+                json!({
+                    "blockHash": "0x4444444444444444444444444444444444444444444444444444444444444444",
+                    "requireCanonical": true
+                }),
+            ),
+        )?;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_read_with_state_override() -> anyhow::Result<()> {
+        let mock_provider = MockProvider::new();
+        mock_provider.push_response(MockResponse::Value(json!("0x000000000000000000000000000000000000000000000000000000000000002a0000000000000000000000001111111111111111111111111111111111111111")));
+
+        let read_contract = ReadableClient::new(Provider::new(mock_provider.clone()));
+
+        let state_override = StateOverride::from([(
+            Address::repeat_byte(0x33),
+            AccountOverride {
+                code: Some(alloy::primitives::Bytes::from(vec![0x60, 0x00])),
+                ..Default::default()
+            },
+        )]);
+        let parameters = ReadContractParametersBuilder::default()
+            .call(fooCall {
+                a: U256::from(42),
+                b: U256::from(10),
+            })
+            .address(Address::repeat_byte(0x22))
+            .block(BlockId::Number(BlockNumberOrTag::Number(16)))
+            .state_override(state_override.clone())
+            .build()?;
+        let transaction = AlloyTransactionRequest::new()
+            .with_to(Some(parameters.address))
+            .with_data(Some(parameters.call.abi_encode()))
+            .to_typed(TransactionKind::Eip1559);
+
+        let result = read_contract.read(parameters).await?;
+        assert_eq!(result._0.bar, U256::from(42));
+
+        // the override is sent as the third eth_call param
+        mock_provider.assert_request("eth_call", (transaction, "0x10", state_override))?;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_read_with_block_overrides() -> anyhow::Result<()> {
+        let mock_provider = MockProvider::new();
+        mock_provider.push_response(MockResponse::Value(json!("0x000000000000000000000000000000000000000000000000000000000000002a0000000000000000000000001111111111111111111111111111111111111111")));
+
+        let read_contract = ReadableClient::new(Provider::new(mock_provider.clone()));
+
+        let block_overrides = BlockOverrides {
+            time: Some(U64::from(1_700_000_000)),
+            ..Default::default()
+        };
+        let parameters = ReadContractParametersBuilder::default()
+            .call(fooCall {
+                a: U256::from(42),
+                b: U256::from(10),
+            })
+            .address(Address::repeat_byte(0x22))
+            .block_overrides(block_overrides.clone())
+            .build()?;
+        let transaction = AlloyTransactionRequest::new()
+            .with_to(Some(parameters.address))
+            .with_data(Some(parameters.call.abi_encode()))
+            .to_typed(TransactionKind::Eip1559);
+
+        let result = read_contract.read(parameters).await?;
+        assert_eq!(result._0.baz, Address::repeat_byte(0x11));
+
+        // the state override is sent empty to reach the fourth param
+        mock_provider.assert_request(
+            "eth_call",
+            (transaction, "latest", json!({}), block_overrides),
+        )?;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_read_block_overrides_unsupported() -> anyhow::Result<()> {
+        let mock_provider = MockProvider::new();
+        mock_provider.push_response(MockResponse::Error(JsonRpcError {
+            code: -32602,
+            message: "too many arguments, want at most 3".to_string(),
+            data: None,
+        }));
+
+        let read_contract = ReadableClient::new(Provider::new(mock_provider));
+
+        let parameters = || {
+            ReadContractParametersBuilder::default()
+                .call(fooCall {
+                    a: U256::from(42),
+                    b: U256::from(10),
+                })
+                .address(Address::repeat_byte(0x22))
+                .block_overrides(BlockOverrides {
+                    number: Some(U256::from(100)),
+                    ..Default::default()
+                })
+                .build()
+        };
+
+        let err = read_contract.read(parameters()?).await.unwrap_err();
+        assert!(
+            matches!(err, ReadableClientError::ReadBlockOverridesUnsupported(ref msg) if msg.contains("too many arguments")),
+            "unexpected error: {err}"
+        );
+
+        // invalid params about the call itself are not taken as unsupported
+        // block overrides
+        let mock_provider = MockProvider::new();
+        mock_provider.push_response(MockResponse::Error(JsonRpcError {
+            code: -32602,
+            message: "invalid argument 1: unknown block".to_string(),
+            data: None,
+        }));
+        let read_contract = ReadableClient::new(Provider::new(mock_provider));
+        let err = read_contract.read(parameters()?).await.unwrap_err();
+        assert!(
+            matches!(err, ReadableClientError::ReadCallError(_)),
+            "unexpected error: {err}"
+        );
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_get_chainid() -> anyhow::Result<()> {
         // Create a mock Provider
@@ -253,6 +530,66 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_detect_transaction_kind() -> anyhow::Result<()> {
+        let genesis_block: serde_json::Value =
+            serde_json::from_str(include_str!("../../fixtures/genesis_block.json"))?;
+        let mut london_block = genesis_block.clone();
+        london_block["baseFeePerGas"] = json!("0x7");
+
+        // responses are served last in first out
+        let mock_provider = MockProvider::new();
+        mock_provider.push_response(MockResponse::Value(london_block));
+        mock_provider.push_response(MockResponse::Value(genesis_block));
+
+        let read_contract = ReadableClient::new(Provider::new(mock_provider));
+
+        assert_eq!(
+            read_contract.detect_transaction_kind().await?,
+            TransactionKind::Legacy
+        );
+        assert_eq!(
+            read_contract.detect_transaction_kind().await?,
+            TransactionKind::Eip1559
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_node_error() -> anyhow::Result<()> {
+        let mock_provider = MockProvider::new();
+        mock_provider.push_response(MockResponse::Error(JsonRpcError {
+            code: -32000,
+            message: "header not found".to_string(),
+            data: None,
+        }));
+        mock_provider.push_response(MockResponse::Error(JsonRpcError {
+            code: 429,
+            message: "Too Many Requests".to_string(),
+            data: None,
+        }));
+
+        let read_contract = ReadableClient::new(Provider::new(mock_provider));
+
+        // responses are served last in first out
+        let err = read_contract.get_chainid().await.unwrap_err();
+        assert_eq!(err.node_error(), Some(NodeErrorKind::RateLimited));
+
+        let parameters = ReadContractParametersBuilder::default()
+            .call(fooCall {
+                a: U256::from(42),
+                b: U256::from(10),
+            })
+            .address(Address::repeat_byte(0x22))
+            .build()?;
+        let err = read_contract.read(parameters).await.unwrap_err();
+        assert!(matches!(err, ReadableClientError::ReadCallError(_)));
+        assert_eq!(err.node_error(), Some(NodeErrorKind::HeaderNotFound));
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_decodable_error() -> anyhow::Result<()> {
         // Create a mock Provider
@@ -289,6 +626,7 @@ mod tests {
         assert!(result.is_err());
 
         let err = result.err().unwrap();
+        assert_eq!(err.node_error(), Some(NodeErrorKind::ExecutionReverted));
 
         match err {
             ReadableClientError::AbiDecodedErrorType(AbiDecodedErrorType::Known {
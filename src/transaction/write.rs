@@ -1,6 +1,13 @@
-use crate::request_shim::{AlloyTransactionRequest, TransactionRequestShim};
+use crate::authorization::{HashSigner, SignedAuthorization};
+use crate::request_shim::{
+    AlloyTransactionRequest, TransactionKind, TransactionRequestShim, TxValidationError,
+    ValidationContext,
+};
+use crate::rpc::{NodeError, NodeErrorKind};
+use crate::{ToAlloy, ToEthers};
 use alloy::primitives::hex::{decode, FromHexError};
-use alloy::primitives::{Address, U256};
+use alloy::primitives::{Address, U256, U64};
+use alloy::rpc::types::AccessList;
 use alloy::sol_types::SolCall;
 use derive_builder::Builder;
 use ethers::middleware::signer::SignerMiddlewareError;
@@ -18,11 +25,21 @@ use tracing::info;
 #[derive(Error, Debug)]
 pub enum WritableClientError {
     #[error("failed to fill transaction: {0}")]
-    WriteFillTxError(String),
+    WriteFillTxError(NodeError),
     #[error("failed to sign transaction: {0}")]
     WriteSignTxError(String),
     #[error("failed to send transaction: {0}")]
-    WriteSendTxError(String),
+    WriteSendTxError(NodeError),
+    #[error("failed to detect transaction kind: {0}")]
+    WriteTransactionKindError(NodeError),
+    #[error("{0:?} transactions have no EIP-1559 fees, set the gas price instead")]
+    WriteEip1559FeesUnsupported(TransactionKind),
+    #[error("EIP-7702 authorization lists need prepare_eip7702_request")]
+    WriteAuthorizationListUnsupported,
+    #[error("invalid transaction request: {0:?}")]
+    WriteValidationError(Vec<TxValidationError>),
+    #[error("failed to fetch the chain state to validate against: {0}")]
+    WriteValidationFetchError(NodeError),
     #[error(transparent)]
     WriteConfirmationError(ProviderError),
     #[error("transaction failed")]
@@ -35,6 +52,23 @@ pub enum WritableClientError {
     HexDecodeError(#[from] FromHexError),
 }
 
+impl WritableClientError {
+    /// The well known node error behind this error, if any
+    pub fn node_error(&self) -> Option<NodeErrorKind> {
+        match self {
+            WritableClientError::WriteFillTxError(err)
+            | WritableClientError::WriteSendTxError(err)
+            | WritableClientError::WriteTransactionKindError(err)
+            | WritableClientError::WriteValidationFetchError(err) => err.kind,
+            WritableClientError::WriteConfirmationError(err) => {
+                NodeErrorKind::from_middleware_error(err)
+            }
+            WritableClientError::AbiDecodedErrorType(_) => Some(NodeErrorKind::ExecutionReverted),
+            _ => None,
+        }
+    }
+}
+
 #[derive(Builder, Clone, Debug)]
 pub struct WriteContractParameters<C: SolCall> {
     pub call: C,
@@ -51,7 +85,48 @@ pub struct WriteContractParameters<C: SolCall> {
     pub nonce: Option<U256>,
     #[builder(setter(into), default)]
     pub value: Option<U256>,
+    #[builder(setter(into), default)]
+    pub access_list: Option<AccessList>,
+    /// Type of the transaction, detected from the latest block if not set
+    #[builder(setter(into), default)]
+    pub transaction_kind: Option<TransactionKind>,
+    /// EIP-7702 authorizations, such transactions can only be prepared with
+    /// [WritableClient::prepare_eip7702_request]
+    #[builder(setter(into), default)]
+    pub authorization_list: Option<Vec<SignedAuthorization>>,
+    /// Skips the pre-flight validation of [WritableClient::prepare_request]
+    /// and [WritableClient::prepare_eip7702_request]
+    #[builder(default)]
+    pub skip_validation: bool,
 }
+
+fn transaction_request<C: SolCall>(
+    parameters: &WriteContractParameters<C>,
+) -> AlloyTransactionRequest {
+    AlloyTransactionRequest::new()
+        .with_to(Some(parameters.address))
+        .with_data(Some(parameters.call.abi_encode()))
+        .with_gas(parameters.gas)
+        .with_max_fee_per_gas(parameters.max_fee_per_gas)
+        .with_gas_price(parameters.gas_price)
+        .with_max_priority_fee_per_gas(parameters.max_priority_fee_per_gas)
+        .with_nonce(parameters.nonce)
+        .with_value(parameters.value)
+        .with_access_list(parameters.access_list.clone())
+        .with_authorization_list(parameters.authorization_list.clone())
+}
+
+/// Request of the parameters for the ethers typed transactions, which cannot
+/// hold an EIP-7702 authorization list
+fn typed_transaction_request<C: SolCall>(
+    parameters: &WriteContractParameters<C>,
+) -> Result<AlloyTransactionRequest, WritableClientError> {
+    if parameters.authorization_list.is_some() {
+        return Err(WritableClientError::WriteAuthorizationListUnsupported);
+    }
+    Ok(transaction_request(parameters))
+}
+
 #[derive(Clone)]
 pub struct WritableClient<M: Middleware, S: Signer>(SignerMiddleware<M, S>);
 
@@ -98,16 +173,11 @@ impl<M: Middleware, S: Signer> WritableClient<M, S> {
         &self,
         parameters: WriteContractParameters<C>,
     ) -> Result<ethers::providers::PendingTransaction<'_, M::Provider>, WritableClientError> {
-        let transaction_request = AlloyTransactionRequest::new()
-            .with_to(Some(parameters.address))
-            .with_data(Some(parameters.call.abi_encode()))
-            .with_gas(parameters.gas)
-            .with_max_fee_per_gas(parameters.max_fee_per_gas)
-            .with_max_priority_fee_per_gas(parameters.max_priority_fee_per_gas)
-            .with_nonce(parameters.nonce)
-            .with_value(parameters.value);
+        let transaction_request = typed_transaction_request(&parameters)?;
 
-        let ethers_transaction_request = transaction_request.to_eip1559();
+        let ethers_transaction_request = self
+            .typed_transaction(&transaction_request, parameters.transaction_kind)
+            .await?;
 
         let res = self
             .0
@@ -115,6 +185,7 @@ impl<M: Middleware, S: Signer> WritableClient<M, S> {
             .await;
 
         let pending_tx = if let Err(err) = res {
+            let node_error = NodeError::from_middleware_error(&err);
             if let SignerMiddlewareError::MiddlewareError(err) = err {
                 if let Some(rpc_err) = err.as_error_response() {
                     match rpc_err.data.clone() {
@@ -125,19 +196,24 @@ impl<M: Middleware, S: Signer> WritableClient<M, S> {
                                 data_slice.as_slice(),
                             )
                             .await?;
-                            return Err(WritableClientError::WriteSendTxError(err.to_string()));
+                            return Err(WritableClientError::WriteSendTxError(NodeError {
+                                message: err.to_string(),
+                                ..node_error
+                            }));
                         }
                         None => {
-                            return Err(WritableClientError::WriteSendTxError(err.to_string()));
+                            return Err(WritableClientError::WriteSendTxError(node_error));
                         }
                     }
                 }
-                return Err(WritableClientError::WriteSendTxError(err.to_string()));
+                return Err(WritableClientError::WriteSendTxError(node_error));
             } else {
-                return Err(WritableClientError::WriteSendTxError(err.to_string()));
+                return Err(WritableClientError::WriteSendTxError(node_error));
             }
         } else {
-            res.map_err(|e| WritableClientError::WriteSendTxError(e.to_string()))?
+            res.map_err(|e| {
+                WritableClientError::WriteSendTxError(NodeError::from_middleware_error(&e))
+            })?
         };
 
         Ok(pending_tx)
@@ -147,26 +223,118 @@ impl<M: Middleware, S: Signer> WritableClient<M, S> {
         &self,
         parameters: WriteContractParameters<C>,
     ) -> Result<TypedTransaction, WritableClientError> {
-        let transaction_request = AlloyTransactionRequest::new()
-            .with_to(Some(parameters.address))
-            .with_data(Some(parameters.call.abi_encode()))
-            .with_gas(parameters.gas)
-            .with_max_fee_per_gas(parameters.max_fee_per_gas)
-            .with_max_priority_fee_per_gas(parameters.max_priority_fee_per_gas)
-            .with_nonce(parameters.nonce)
-            .with_value(parameters.value);
-
-        let eip1559_request = transaction_request.to_eip1559();
-
-        let mut tx = TypedTransaction::Eip1559(eip1559_request);
-        self.0
-            .fill_transaction(&mut tx, None)
-            .await
-            .map_err(|e| WritableClientError::WriteFillTxError(e.to_string()))?;
+        let transaction_request = typed_transaction_request(&parameters)?;
+
+        if !parameters.skip_validation {
+            self.validate_request(&transaction_request).await?;
+        }
+
+        let mut tx = self
+            .typed_transaction(&transaction_request, parameters.transaction_kind)
+            .await?;
+        self.0.fill_transaction(&mut tx, None).await.map_err(|e| {
+            WritableClientError::WriteFillTxError(NodeError::from_middleware_error(&e))
+        })?;
 
         Ok(tx)
     }
 
+    /// Validates the request, signed for the signer chain id, against the
+    /// node chain id and, if the request sets its gas, the latest block gas
+    /// limit
+    async fn validate_request(
+        &self,
+        transaction_request: &AlloyTransactionRequest,
+    ) -> Result<(), WritableClientError> {
+        let transaction_request = transaction_request
+            .clone()
+            .with_chain_id(Some(self.0.signer().chain_id()));
+
+        let chain_id = self.0.get_chainid().await.map_err(|e| {
+            WritableClientError::WriteValidationFetchError(NodeError::from_middleware_error(&e))
+        })?;
+        let mut context = ValidationContext {
+            chain_id: Some(U64::from(chain_id.as_u64())),
+            block_gas_limit: None,
+        };
+        if transaction_request.gas.is_some() {
+            context.block_gas_limit = self
+                .0
+                .get_block(ethers::types::BlockNumber::Latest)
+                .await
+                .map_err(|e| {
+                    WritableClientError::WriteValidationFetchError(
+                        NodeError::from_middleware_error(&e),
+                    )
+                })?
+                .map(|block| block.gas_limit.to_alloy());
+        }
+
+        let errors = transaction_request.validate_against(&context);
+        if !errors.is_empty() {
+            return Err(WritableClientError::WriteValidationError(errors));
+        }
+        Ok(())
+    }
+
+    /// Detects the transaction kind the chain supports from the latest block,
+    /// EIP-1559 if it has a base fee and legacy otherwise
+    pub async fn detect_transaction_kind(&self) -> Result<TransactionKind, WritableClientError> {
+        super::detect_transaction_kind(&self.0)
+            .await
+            .map_err(WritableClientError::WriteTransactionKindError)
+    }
+
+    /// The request as the configured transaction kind, detected if not set.
+    /// Legacy and EIP-2930 transactions only have a gas price, so EIP-1559
+    /// fees are rejected for them rather than dropped.
+    async fn typed_transaction(
+        &self,
+        transaction_request: &AlloyTransactionRequest,
+        transaction_kind: Option<TransactionKind>,
+    ) -> Result<TypedTransaction, WritableClientError> {
+        let transaction_kind = match transaction_kind {
+            Some(transaction_kind) => transaction_kind,
+            None => self.detect_transaction_kind().await?,
+        };
+        if transaction_kind != TransactionKind::Eip1559
+            && (transaction_request.max_fee_per_gas.is_some()
+                || transaction_request.max_priority_fee_per_gas.is_some())
+        {
+            return Err(WritableClientError::WriteEip1559FeesUnsupported(
+                transaction_kind,
+            ));
+        }
+        Ok(transaction_request.to_typed(transaction_kind))
+    }
+
+    /// Prepares an EIP-7702 set code transaction, which ethers
+    /// [TypedTransaction] cannot represent, for [WritableClient::sign_eip7702_request].
+    /// The chain id is the signer one, a missing nonce is taken from the signer
+    /// account, gas and fees have to be set. The request is validated as in
+    /// [WritableClient::prepare_request].
+    pub async fn prepare_eip7702_request<C: SolCall>(
+        &self,
+        parameters: WriteContractParameters<C>,
+    ) -> Result<AlloyTransactionRequest, WritableClientError> {
+        let mut transaction_request =
+            transaction_request(&parameters).with_chain_id(Some(self.0.signer().chain_id()));
+        if !parameters.skip_validation {
+            self.validate_request(&transaction_request).await?;
+        }
+        if transaction_request.nonce.is_none() {
+            let nonce = self
+                .0
+                .get_transaction_count(self.0.signer().address(), None)
+                .await
+                .map_err(|e| {
+                    WritableClientError::WriteFillTxError(NodeError::from_middleware_error(&e))
+                })?;
+            transaction_request = transaction_request.with_nonce(Some(nonce.to_alloy()));
+        }
+        Ok(transaction_request)
+    }
+
     pub async fn sign_request(&self, tx: TypedTransaction) -> Result<Bytes, WritableClientError> {
         let signature = self
             .0
@@ -181,21 +349,37 @@ impl<M: Middleware, S: Signer> WritableClient<M, S> {
         &self,
         bytes: Bytes,
     ) -> Result<PendingTransaction<'_, M::Provider>, WritableClientError> {
-        self.0
-            .send_raw_transaction(bytes)
-            .await
-            .map_err(|e| WritableClientError::WriteSendTxError(e.to_string()))
+        self.0.send_raw_transaction(bytes).await.map_err(|e| {
+            WritableClientError::WriteSendTxError(NodeError::from_middleware_error(&e))
+        })
+    }
+}
+
+impl<M: Middleware, S: Signer + HashSigner> WritableClient<M, S> {
+    /// Signs an EIP-7702 set code transaction from
+    /// [WritableClient::prepare_eip7702_request], returning its type 4
+    /// EIP-2718 encoding for [WritableClient::send_request]
+    pub async fn sign_eip7702_request(
+        &self,
+        transaction_request: AlloyTransactionRequest,
+    ) -> Result<Bytes, WritableClientError> {
+        transaction_request
+            .sign_eip7702(self.0.signer())
+            .map(ToEthers::to_ethers)
+            .map_err(|e| WritableClientError::WriteSignTxError(e.to_string()))
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::authorization::Authorization;
     use crate::transaction::mock_middleware::{MockJsonRpcClient, MockMiddleware};
-    use alloy::primitives::{Address, U256};
+    use alloy::primitives::{Address, B256, U256};
+    use alloy::rpc::types::AccessListItem;
     use alloy::sol;
     use ethers::core::rand::thread_rng;
-    use ethers::providers::Provider;
+    use ethers::providers::{HttpClientError, JsonRpcError, Provider};
     use ethers::signers::LocalWallet;
     use ethers::types::{Bytes, H160};
     use tracing_subscriber;
@@ -238,11 +422,21 @@ mod tests {
             .max_priority_fee_per_gas(U256::from(100000))
             .nonce(Some(U256::from(100000)))
             .value(Some(U256::from(100000)))
+            .transaction_kind(TransactionKind::Eip2930)
+            .access_list(Some(AccessList(vec![AccessListItem {
+                address: Address::repeat_byte(0x22),
+                storage_keys: vec![B256::with_last_byte(1)],
+            }])))
             .build()?;
 
         assert_eq!(parameters.address, Address::repeat_byte(0x11));
         assert_eq!(parameters.call.a, U256::from(42));
         assert_eq!(parameters.call.b, U256::from(10));
+        assert_eq!(
+            parameters.access_list.unwrap().0[0].address,
+            Address::repeat_byte(0x22)
+        );
+        assert_eq!(parameters.transaction_kind, Some(TransactionKind::Eip2930));
 
         Ok(())
     }
@@ -283,6 +477,357 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_access_list_forwarded() -> anyhow::Result<()> {
+        let access_list = AccessList(vec![AccessListItem {
+            address: Address::repeat_byte(0x33),
+            storage_keys: vec![B256::with_last_byte(1)],
+        }]);
+        let parameters = WriteContractParametersBuilder::default()
+            .call(fooCall {
+                a: U256::from(42),
+                b: U256::from(10),
+            })
+            .address(Address::repeat_byte(0x22))
+            .access_list(Some(access_list.clone()))
+            .build()?;
+
+        let provider = Provider::new(MockJsonRpcClient::new());
+        let mut mock_middleware = MockMiddleware::new(provider)?;
+        mock_middleware.assert_next_access_list(access_list.clone().to_ethers());
+        let wallet = LocalWallet::new(&mut thread_rng());
+        let writable_client = WritableClient::new(SignerMiddleware::new(mock_middleware, wallet));
+
+        for transaction_kind in [TransactionKind::Eip2930, TransactionKind::Eip1559] {
+            let tx = writable_client
+                .prepare_request(WriteContractParameters {
+                    transaction_kind: Some(transaction_kind),
+                    ..parameters.clone()
+                })
+                .await?;
+            assert_eq!(tx.access_list(), Some(&access_list.clone().to_ethers()));
+        }
+
+        let parameters = WriteContractParameters {
+            transaction_kind: Some(TransactionKind::Eip1559),
+            ..parameters
+        };
+        let _ = writable_client.write(parameters).await?;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_prepare_legacy_request() -> anyhow::Result<()> {
+        let provider = Provider::new(MockJsonRpcClient::new());
+        let mock_middleware = MockMiddleware::new(provider)?;
+        let wallet = LocalWallet::new(&mut thread_rng());
+        let writable_client = WritableClient::new(SignerMiddleware::new(mock_middleware, wallet));
+
+        // the mock middleware latest block has no base fee
+        let transaction_kind = writable_client.detect_transaction_kind().await?;
+        assert_eq!(transaction_kind, TransactionKind::Legacy);
+
+        let parameters = WriteContractParametersBuilder::default()
+            .call(fooCall {
+                a: U256::from(42),
+                b: U256::from(10),
+            })
+            .address(Address::repeat_byte(0x22))
+            .gas_price(Some(U256::from(1000)))
+            .transaction_kind(transaction_kind)
+            .build()?;
+
+        // the kind is detected when not set
+        let tx = writable_client
+            .prepare_request(WriteContractParameters {
+                transaction_kind: None,
+                ..parameters.clone()
+            })
+            .await?;
+        assert!(matches!(tx, TypedTransaction::Legacy(_)));
+
+        let tx = writable_client.prepare_request(parameters).await?;
+        let TypedTransaction::Legacy(tx) = tx else {
+            panic!("expected a legacy transaction");
+        };
+        assert_eq!(tx.gas_price, Some(ethers::types::U256::from(1000)));
+        assert_eq!(
+            tx.to,
+            Some(ethers::types::NameOrAddress::Address(H160::repeat_byte(
+                0x22
+            )))
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_prepare_request_eip1559_fees() -> anyhow::Result<()> {
+        let provider = Provider::new(MockJsonRpcClient::new());
+        let mock_middleware = MockMiddleware::new(provider)?;
+        let wallet = LocalWallet::new(&mut thread_rng());
+        let writable_client = WritableClient::new(SignerMiddleware::new(mock_middleware, wallet));
+
+        let parameters = WriteContractParametersBuilder::default()
+            .call(fooCall {
+                a: U256::from(42),
+                b: U256::from(10),
+            })
+            .address(Address::repeat_byte(0x22))
+            .max_fee_per_gas(Some(U256::from(200)))
+            .max_priority_fee_per_gas(Some(U256::from(100)))
+            .build()?;
+
+        // the mock middleware latest block has no base fee, so the detected
+        // legacy kind cannot carry the fees
+        let err = writable_client
+            .prepare_request(parameters.clone())
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            WritableClientError::WriteEip1559FeesUnsupported(TransactionKind::Legacy)
+        ));
+
+        let err = writable_client
+            .prepare_request(WriteContractParameters {
+                transaction_kind: Some(TransactionKind::Eip2930),
+                max_fee_per_gas: None,
+                ..parameters.clone()
+            })
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            WritableClientError::WriteEip1559FeesUnsupported(TransactionKind::Eip2930)
+        ));
+
+        let tx = writable_client
+            .prepare_request(WriteContractParameters {
+                transaction_kind: Some(TransactionKind::Eip1559),
+                ..parameters
+            })
+            .await?;
+        assert_eq!(
+            tx.as_eip1559_ref().unwrap().max_fee_per_gas,
+            Some(ethers::types::U256::from(200))
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_prepare_request_validation() -> anyhow::Result<()> {
+        let provider = Provider::new(MockJsonRpcClient::new());
+        let mock_middleware = MockMiddleware::new(provider)?;
+        let wallet = LocalWallet::new(&mut thread_rng());
+        let writable_client = WritableClient::new(SignerMiddleware::new(mock_middleware, wallet));
+
+        let parameters = WriteContractParametersBuilder::default()
+            .call(fooCall {
+                a: U256::from(42),
+                b: U256::from(10),
+            })
+            .address(Address::repeat_byte(0x22))
+            .max_fee_per_gas(Some(U256::from(100)))
+            .max_priority_fee_per_gas(Some(U256::from(200)))
+            .build()?;
+
+        let err = writable_client
+            .prepare_request(parameters.clone())
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            WritableClientError::WriteValidationError(errors)
+                if errors == vec![TxValidationError::PriorityFeeAboveMaxFee {
+                    max_priority_fee_per_gas: U256::from(200),
+                    max_fee_per_gas: U256::from(100),
+                }]
+        ));
+
+        // the mock middleware latest block has a zero gas limit
+        let err = writable_client
+            .prepare_request(WriteContractParameters {
+                max_priority_fee_per_gas: None,
+                gas: Some(U256::from(100000)),
+                ..parameters.clone()
+            })
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            WritableClientError::WriteValidationError(errors)
+                if matches!(errors[..], [TxValidationError::GasAboveBlockLimit { .. }])
+        ));
+
+        let parameters = WriteContractParameters {
+            skip_validation: true,
+            ..parameters
+        };
+        writable_client.prepare_request(parameters.clone()).await?;
+
+        // the mock node is on chain 1
+        let wallet = LocalWallet::new(&mut thread_rng()).with_chain_id(137_u64);
+        let writable_client = WritableClient::new(SignerMiddleware::new(
+            MockMiddleware::new(Provider::new(MockJsonRpcClient::new()))?,
+            wallet,
+        ));
+        let err = writable_client
+            .prepare_request(WriteContractParameters {
+                skip_validation: false,
+                max_priority_fee_per_gas: None,
+                ..parameters
+            })
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            WritableClientError::WriteValidationError(errors)
+                if errors == vec![TxValidationError::ChainIdMismatch {
+                    expected: U64::from(1),
+                    actual: U64::from(137),
+                }]
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_node_error() {
+        let err = WritableClientError::WriteSendTxError(NodeError::from_middleware_error(
+            &ProviderError::from(HttpClientError::JsonRpcError(JsonRpcError {
+                code: -32000,
+                message: "nonce too low".to_string(),
+                data: None,
+            })),
+        ));
+        assert_eq!(err.node_error(), Some(NodeErrorKind::NonceTooLow));
+
+        let err = WritableClientError::WriteConfirmationError(ProviderError::from(
+            HttpClientError::JsonRpcError(JsonRpcError {
+                code: -32000,
+                message: "replacement transaction underpriced".to_string(),
+                data: None,
+            }),
+        ));
+        assert_eq!(
+            err.node_error(),
+            Some(NodeErrorKind::ReplacementUnderpriced)
+        );
+
+        // errors that are not node error responses are not classified from
+        // their message
+        let err = WritableClientError::WriteConfirmationError(ProviderError::CustomError(
+            "replacement transaction underpriced".to_string(),
+        ));
+        assert_eq!(err.node_error(), None);
+
+        let err = WritableClientError::WriteSendTxError(NodeError::from(
+            "Transaction did not receive 4 confirmations".to_string(),
+        ));
+        assert_eq!(err.node_error(), None);
+
+        assert_eq!(WritableClientError::WriteFailedTxError().node_error(), None);
+    }
+
+    #[tokio::test]
+    async fn test_sign_eip7702_request() -> anyhow::Result<()> {
+        let provider = Provider::new(MockJsonRpcClient::new());
+        let mock_middleware = MockMiddleware::new(provider)?;
+        let wallet = LocalWallet::new(&mut thread_rng()).with_chain_id(137_u64);
+        let writable_client = WritableClient::new(SignerMiddleware::new(mock_middleware, wallet));
+
+        let authority = LocalWallet::new(&mut thread_rng());
+        let authorization = Authorization {
+            chain_id: U256::from(137),
+            address: Address::repeat_byte(0x33),
+            nonce: U64::ZERO,
+        }
+        .sign(&authority)?;
+
+        let parameters = WriteContractParametersBuilder::default()
+            .call(fooCall {
+                a: U256::from(42),
+                b: U256::from(10),
+            })
+            .address(Address::repeat_byte(0x22))
+            .gas(Some(U256::from(100000)))
+            .max_fee_per_gas(Some(U256::from(200)))
+            .max_priority_fee_per_gas(Some(U256::from(100)))
+            .authorization_list(Some(vec![authorization]))
+            // the mock node is on chain 1 and its latest block has a zero gas
+            // limit
+            .skip_validation(true)
+            .build()?;
+
+        // typed transactions cannot carry the authorization list
+        assert!(matches!(
+            writable_client.prepare_request(parameters.clone()).await,
+            Err(WritableClientError::WriteAuthorizationListUnsupported)
+        ));
+
+        let transaction_request = writable_client.prepare_eip7702_request(parameters).await?;
+        let encoded = writable_client
+            .sign_eip7702_request(transaction_request)
+            .await?;
+        assert_eq!(encoded[0], 0x04);
+        let rlp = ethers::utils::rlp::Rlp::new(&encoded[1..]);
+        assert_eq!(rlp.val_at::<u64>(0)?, 137);
+        // nonce of the mock client
+        assert_eq!(rlp.val_at::<u64>(1)?, 0x10);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_prepare_eip7702_request_validation() -> anyhow::Result<()> {
+        let provider = Provider::new(MockJsonRpcClient::new());
+        let mock_middleware = MockMiddleware::new(provider)?;
+        let wallet = LocalWallet::new(&mut thread_rng());
+        let writable_client = WritableClient::new(SignerMiddleware::new(mock_middleware, wallet));
+
+        let authorization = Authorization {
+            chain_id: U256::from(1),
+            address: Address::repeat_byte(0x33),
+            nonce: U64::ZERO,
+        }
+        .sign(&LocalWallet::new(&mut thread_rng()))?;
+
+        let parameters = WriteContractParametersBuilder::default()
+            .call(fooCall {
+                a: U256::from(42),
+                b: U256::from(10),
+            })
+            .address(Address::repeat_byte(0x22))
+            .max_fee_per_gas(Some(U256::from(100)))
+            .max_priority_fee_per_gas(Some(U256::from(200)))
+            .authorization_list(Some(vec![authorization]))
+            .build()?;
+
+        let err = writable_client
+            .prepare_eip7702_request(parameters.clone())
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            WritableClientError::WriteValidationError(errors)
+                if errors == vec![TxValidationError::PriorityFeeAboveMaxFee {
+                    max_priority_fee_per_gas: U256::from(200),
+                    max_fee_per_gas: U256::from(100),
+                }]
+        ));
+
+        let parameters = WriteContractParameters {
+            skip_validation: true,
+            ..parameters
+        };
+        writable_client.prepare_eip7702_request(parameters).await?;
+
+        Ok(())
+    }
+
     #[allow(dead_code)]
     fn setup_tracing() {
         let subscriber = FmtSubscriber::builder()
@@ -1,3 +1,4 @@
+use crate::rpc::NodeError;
 use crate::transaction::{WritableClient, WritableClientError, WriteContractParameters};
 use alloy::sol_types::SolCall;
 use ethers::middleware::SignerMiddleware;
@@ -83,9 +84,11 @@ impl<M: Middleware, S: Signer, C: SolCall + Clone, F: Fn(WriteTransactionStatus<
                 .confirmations(self.confirmations.into())
                 .await
                 .map_err(WritableClientError::WriteConfirmationError)?
-                .ok_or(WritableClientError::WriteSendTxError(format!(
-                    "Transaction did not receive {} confirmations",
-                    self.confirmations,
+                .ok_or(WritableClientError::WriteSendTxError(NodeError::from(
+                    format!(
+                        "Transaction did not receive {} confirmations",
+                        self.confirmations,
+                    ),
                 )))?;
             self.update_status(WriteTransactionStatus::Confirmed(receipt));
         }